@@ -0,0 +1,278 @@
+use crate::workspace::{LocaleFields, METADATA_FIELDS};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Modified => "modified",
+        }
+    }
+}
+
+/// One field that differs between two sets of localizations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub locale: String,
+    pub field: String,
+    pub kind: ChangeKind,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Compare two sets of localizations field by field (`old` → `new`).
+/// Empty and missing values are treated the same.
+pub fn diff_locales(
+    old: &BTreeMap<String, LocaleFields>,
+    new: &BTreeMap<String, LocaleFields>,
+) -> Vec<FieldChange> {
+    let empty = LocaleFields::new();
+    let mut locales: Vec<&String> = old.keys().chain(new.keys()).collect();
+    locales.sort();
+    locales.dedup();
+
+    let mut changes = Vec::new();
+    for locale in locales {
+        let old_fields = old.get(locale).unwrap_or(&empty);
+        let new_fields = new.get(locale).unwrap_or(&empty);
+        changes.extend(diff_fields(locale, old_fields, new_fields));
+    }
+    changes
+}
+
+/// Compare the fields of a single locale, in `METADATA_FIELDS` order
+pub fn diff_fields(locale: &str, old: &LocaleFields, new: &LocaleFields) -> Vec<FieldChange> {
+    let non_empty = |fields: &LocaleFields, field: &str| {
+        fields.get(field).filter(|v| !v.trim().is_empty()).cloned()
+    };
+
+    let mut changes = Vec::new();
+    for field in METADATA_FIELDS {
+        let old_value = non_empty(old, field);
+        let new_value = non_empty(new, field);
+
+        let kind = match (&old_value, &new_value) {
+            (None, None) => continue,
+            (None, Some(_)) => ChangeKind::Added,
+            (Some(_), None) => ChangeKind::Removed,
+            (Some(a), Some(b)) if a == b => continue,
+            (Some(_), Some(_)) => ChangeKind::Modified,
+        };
+
+        changes.push(FieldChange {
+            locale: locale.to_string(),
+            field: field.to_string(),
+            kind,
+            old: old_value,
+            new: new_value,
+        });
+    }
+    changes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenshotChangeKind {
+    Added,
+    Removed,
+    Moved,
+}
+
+/// One screenshot that was added, removed or moved within a display type's set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotChange {
+    pub locale: String,
+    pub display_type: String,
+    pub kind: ScreenshotChangeKind,
+    pub item: String,
+    /// 1-based position in the old set
+    pub from_position: Option<usize>,
+    /// 1-based position in the new set
+    pub to_position: Option<usize>,
+}
+
+/// Compare two `screenshots.json` manifests of the same locale, set by set.
+/// Items are matched by file name without the ordinal prefix, since screenshot IDs change between versions.
+pub fn diff_screenshot_manifests(locale: &str, old: Option<&Value>, new: Option<&Value>) -> Vec<ScreenshotChange> {
+    let old_sets = manifest_sets(old);
+    let new_sets = manifest_sets(new);
+
+    let mut display_types: Vec<&String> = old_sets.keys().chain(new_sets.keys()).collect();
+    display_types.sort();
+    display_types.dedup();
+
+    let mut changes = Vec::new();
+    for display_type in display_types {
+        let empty = Vec::new();
+        let old_items = old_sets.get(display_type).unwrap_or(&empty);
+        let new_items = new_sets.get(display_type).unwrap_or(&empty);
+
+        let change = |kind, item: &String, from_position, to_position| ScreenshotChange {
+            locale: locale.to_string(),
+            display_type: display_type.clone(),
+            kind,
+            item: item.clone(),
+            from_position,
+            to_position,
+        };
+
+        for (index, item) in old_items.iter().enumerate() {
+            if !new_items.contains(item) {
+                changes.push(change(ScreenshotChangeKind::Removed, item, Some(index + 1), None));
+            }
+        }
+        for (index, item) in new_items.iter().enumerate() {
+            if !old_items.contains(item) {
+                changes.push(change(ScreenshotChangeKind::Added, item, None, Some(index + 1)));
+            }
+        }
+
+        // Items kept in both sets but outside their longest common ordering were moved
+        let kept_old: Vec<&String> = old_items.iter().filter(|i| new_items.contains(i)).collect();
+        let kept_new: Vec<&String> = new_items.iter().filter(|i| old_items.contains(i)).collect();
+        for op in capture_diff_slices(Algorithm::Myers, &kept_old, &kept_new) {
+            if let DiffOp::Insert { new_index, new_len, .. } | DiffOp::Replace { new_index, new_len, .. } = op {
+                for item in &kept_new[new_index..new_index + new_len] {
+                    let from = old_items.iter().position(|i| i == *item).map(|p| p + 1);
+                    let to = new_items.iter().position(|i| i == *item).map(|p| p + 1);
+                    changes.push(change(ScreenshotChangeKind::Moved, item, from, to));
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Item keys of each screenshot set in a manifest, keyed by display type
+fn manifest_sets(manifest: Option<&Value>) -> BTreeMap<String, Vec<String>> {
+    let mut sets = BTreeMap::new();
+    let Some(manifest_sets) = manifest.and_then(|m| m.get("sets")).and_then(|s| s.as_array()) else {
+        return sets;
+    };
+
+    for set in manifest_sets {
+        let display_type = set.get("displayType").and_then(|v| v.as_str()).unwrap_or("UNKNOWN").to_string();
+        let items = set
+            .get("items")
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().map(screenshot_key).collect())
+            .unwrap_or_default();
+        sets.insert(display_type, items);
+    }
+    sets
+}
+
+/// `screenshots/APP_IPHONE_67/02-home.png` → `home.png`
+fn screenshot_key(item: &Value) -> String {
+    let file_name = item
+        .get("file")
+        .and_then(|v| v.as_str())
+        .and_then(|f| f.rsplit(['/', '\\']).next())
+        .unwrap_or("");
+
+    let without_ordinal = match file_name.split_once('-') {
+        Some((ordinal, rest)) if !ordinal.is_empty() && ordinal.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => file_name,
+    };
+
+    if without_ordinal.is_empty() {
+        item.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string()
+    } else {
+        without_ordinal.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(pairs: &[(&str, &str)]) -> LocaleFields {
+        pairs.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect()
+    }
+
+    fn manifest(files: &[&str]) -> Value {
+        let items: Vec<Value> = files.iter().map(|file| json!({ "file": file })).collect();
+        json!({ "sets": [{ "displayType": "APP_IPHONE_67", "items": items }] })
+    }
+
+    #[test]
+    fn empty_and_missing_fields_are_the_same() {
+        let old = fields(&[("name", "Hands Time"), ("subtitle", "  ")]);
+        let new = fields(&[("name", "Hands Time"), ("keywords", "")]);
+        assert!(diff_fields("en-US", &old, &new).is_empty());
+    }
+
+    #[test]
+    fn fields_are_added_removed_and_modified_in_metadata_order() {
+        let old = fields(&[("description", "Old"), ("name", "Hands"), ("whatsNew", "Fixes")]);
+        let new = fields(&[("description", "New"), ("name", "Hands"), ("subtitle", "Clock")]);
+
+        let changes = diff_fields("ja", &old, &new);
+        let summary: Vec<(&str, ChangeKind)> = changes.iter().map(|c| (c.field.as_str(), c.kind)).collect();
+        assert_eq!(
+            summary,
+            [("subtitle", ChangeKind::Added), ("description", ChangeKind::Modified), ("whatsNew", ChangeKind::Removed)]
+        );
+        assert_eq!(changes[1].old.as_deref(), Some("Old"));
+        assert_eq!(changes[1].new.as_deref(), Some("New"));
+    }
+
+    #[test]
+    fn locales_missing_on_one_side_are_diffed_against_nothing() {
+        let old = BTreeMap::from([("fr-FR".to_string(), fields(&[("name", "Heure")]))]);
+        let new = BTreeMap::from([("de-DE".to_string(), fields(&[("name", "Zeit")]))]);
+
+        let changes = diff_locales(&old, &new);
+        let summary: Vec<(&str, ChangeKind)> = changes.iter().map(|c| (c.locale.as_str(), c.kind)).collect();
+        assert_eq!(summary, [("de-DE", ChangeKind::Added), ("fr-FR", ChangeKind::Removed)]);
+    }
+
+    #[test]
+    fn screenshots_are_matched_by_name_without_ordinal() {
+        let old = manifest(&["screenshots/APP_IPHONE_67/01-home.png", "screenshots/APP_IPHONE_67/02-widgets.png"]);
+        let new = manifest(&["screenshots/APP_IPHONE_67/1-home.png", "screenshots/APP_IPHONE_67/2-widgets.png"]);
+        assert!(diff_screenshot_manifests("en-US", Some(&old), Some(&new)).is_empty());
+    }
+
+    #[test]
+    fn screenshots_added_removed_and_moved() {
+        let old = manifest(&["01-home.png", "02-widgets.png", "03-settings.png", "04-themes.png"]);
+        let new = manifest(&["01-widgets.png", "02-home.png", "03-settings.png", "04-watch.png"]);
+
+        let changes = diff_screenshot_manifests("en-US", Some(&old), Some(&new));
+        let summary: Vec<(ScreenshotChangeKind, &str, Option<usize>, Option<usize>)> = changes
+            .iter()
+            .map(|c| (c.kind, c.item.as_str(), c.from_position, c.to_position))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (ScreenshotChangeKind::Removed, "themes.png", Some(4), None),
+                (ScreenshotChangeKind::Added, "watch.png", None, Some(4)),
+                (ScreenshotChangeKind::Moved, "widgets.png", Some(2), Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn screenshot_sets_missing_from_a_manifest_are_all_added() {
+        let new = manifest(&["01-home.png", "02-widgets.png"]);
+
+        let changes = diff_screenshot_manifests("en-US", None, Some(&new));
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.kind == ScreenshotChangeKind::Added && c.display_type == "APP_IPHONE_67"));
+    }
+}
@@ -1,105 +1,130 @@
-use crate::config::Config;
+use crate::commands::translate::TranslationPlan;
+use crate::config::{AiConfig, Config};
 use crate::formatter::DataFormatter;
+use crate::ledger::{self, Ledger, ReportGroup};
+use crate::locales;
+use crate::provider::pricing::PriceTable;
+use crate::provider::tokens::TokenCounter;
+use crate::provider::TokenUsage;
+use crate::templates::{TemplateResolver, TemplateStore};
+use crate::text_metrics::char_count;
+use crate::workspace::{read_source, Workspace};
 use anyhow::{Context, Result};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::path::Path;
 
-pub async fn run(detailed: bool, config_path: &Path) -> Result<()> {
+#[derive(Debug, Clone, Default)]
+pub struct CostOptions {
+    pub detailed: bool,
+    /// Estimate from the source text on App Store Connect instead of the local workspace
+    pub from_remote: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Group by "month", "app" or "locale"
+    pub by: String,
+    /// Only include this month (YYYY-MM)
+    pub month: Option<String>,
+    pub output_format: String,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            by: "month".to_string(),
+            month: None,
+            output_format: "table".to_string(),
+        }
+    }
+}
+
+pub async fn run(config_path: &Path, options: CostOptions) -> Result<()> {
     println!("Estimating AI API call costs...");
     
     let config = Config::load(config_path)
         .context("Failed to load configuration")?;
     
-    // Initialize Node.js runtime
-    rc_node::init_node_runtime()
-        .context("Failed to initialize Node.js runtime")?;
+    // Source text the translation would start from
+    let source_locale = &config.app.default_locale;
+    let source_fields = read_source(&config, options.from_remote).await?;
     
-    // Get current app data for accurate cost estimation
-    println!("📥 Fetching app metadata for cost calculation...");
-    let app_data = rc_node::asc_download(config.app.bundle_id.clone()).await
-        .context("Failed to download app data")?;
+    let default_ai = AiConfig::default();
+    let ai_config = config.ai.as_ref().unwrap_or(&default_ai);
+    let model = &ai_config.model;
+    let prices = PriceTable::from_config(ai_config);
+    let counter = TokenCounter::for_model(model)?;
+    let templates = TemplateResolver::new(&TemplateStore::open()?, ai_config, None)?;
     
-    // Extract source metadata 
-    let source_locale = &config.app.default_locale;
-    let source_metadata = app_data
-        .get("metadata")
-        .and_then(|m| m.get(source_locale))
-        .context("No source metadata found for cost estimation")?
-        .clone();
-    
-    // Prepare estimation request
-    let estimation_request = json!({
-        "metadata": source_metadata,
-        "sourceLocale": source_locale,
-        "targetLocales": config.app.target_locales,
-        "context": format!("App Store metadata for {}", config.app.bundle_id)
+    // Estimate the fields the next translate run would send to the AI: new or changed source
+    // fields that are not kept as edited or found in the translation memory. Existing translations
+    // predict the output length best; otherwise it scales with the language.
+    let workspace = Workspace::open(&config)?;
+    let plan = TranslationPlan::new(&config, &workspace, &source_fields, &config.app.target_locales, false, false)?;
+    let mut by_locale: BTreeMap<String, TokenUsage> = BTreeMap::new();
+    let mut by_field: BTreeMap<String, TokenUsage> = BTreeMap::new();
+    for request in plan.requests(&config, &source_fields, &templates) {
+        let usage = counter.estimate(&request, plan.existing(&request.target_locale, &request.field));
+        *by_locale.entry(request.target_locale).or_default() += usage;
+        *by_field.entry(request.field).or_default() += usage;
+    }
+    let total = by_locale.values().fold(TokenUsage::default(), |mut total, usage| {
+        total += *usage;
+        total
     });
-    
-    // Get accurate cost estimate
-    let cost_estimate = rc_node::ai_estimate_cost(estimation_request).await
-        .context("Failed to estimate translation cost")?;
+    let cost_of = |usage: TokenUsage| prices.cost(model, usage).unwrap_or(0.0);
+    let estimated_cost = cost_of(total);
     
     println!("💰 Cost Estimation");
     println!("{}", "═".repeat(50));
     println!();
     
-    let estimated_cost = cost_estimate.get("estimatedCost").and_then(|c| c.as_f64()).unwrap_or(0.0);
-    let token_estimate = cost_estimate.get("tokenEstimate").and_then(|t| t.as_u64()).unwrap_or(0);
+    if options.detailed {
+        print!("{}", DataFormatter::format_translation_plan(&plan.pending, &plan.kept, &plan.from_memory, &plan.references));
+    }
     
-    if detailed {
+    if options.detailed {
         println!("📊 Content Analysis");
         println!("{}", "─".repeat(30));
         
-        // Analyze each field for detailed breakdown
-        if let Some(metadata) = source_metadata.as_object() {
-            for (field, value) in metadata {
-                if let Some(text) = value.as_str() {
-                    let field_tokens = (text.len() as f64 / 4.0).ceil() as u64;
-                    let field_cost = (field_tokens as f64 / 1000.0) * 0.0006;
-                    
-                    let (icon, label) = match field.as_str() {
-                        "name" => ("📱", "App Name"),
-                        "description" => ("📝", "Description"),
-                        "keywords" => ("🔍", "Keywords"),
-                        "whatsNew" => ("📢", "What's New"),
-                        _ => ("📄", field.as_str()),
-                    };
-                    
-                    println!("   {} {}: ${:.4} ({} chars, ~{} tokens)", 
-                        icon, label, field_cost, text.len(), field_tokens);
-                }
-            }
+        // Each field across all target locales
+        for (field, usage) in &by_field {
+            let text = source_fields.get(field).map(|v| v.as_str()).unwrap_or("");
+            let (icon, label) = DataFormatter::field_label(field);
+            println!("   {} {}: ${:.4} ({} chars, {} source tokens, {} in / {} out)", 
+                icon, label, cost_of(*usage), char_count(text), counter.count(text), usage.input, usage.output);
         }
         println!();
         
         println!("🌍 Locale Breakdown");
         println!("{}", "─".repeat(30));
-        let cost_per_locale = estimated_cost / (config.app.target_locales.len() as f64).max(1.0);
-        for locale in &config.app.target_locales {
-            let locale_flag = match locale.as_str() {
-                "en-US" => "🇺🇸",
-                "zh-Hans" => "🇨🇳", 
-                "fr-FR" => "🇫🇷",
-                "de-DE" => "🇩🇪",
-                _ => "🌐",
-            };
-            println!("   {} {}: ${:.4}", locale_flag, locale, cost_per_locale);
+        for (locale, usage) in &by_locale {
+            println!("   {} {:<8} ${:.4} ({} in / {} out tokens)", locales::flag(locale), locale, cost_of(*usage), usage.input, usage.output);
         }
         println!();
     }
     
     // Use formatter for cost display
-    let cost_info = DataFormatter::format_cost_info(&cost_estimate);
+    let cost_info = DataFormatter::format_cost_info(&json!({
+        "estimatedCost": estimated_cost,
+        "tokenEstimate": total.input + total.output,
+    }));
     print!("{}", cost_info);
     
     println!("📈 Project Summary");
     println!("{}", "─".repeat(30));
-    println!("🤖 Model: {}", config.ai.as_ref().map(|ai| &ai.model).unwrap_or(&"gpt-4o-mini".to_string()));
+    match prices.price(model) {
+        Some(price) => println!("🤖 Model: {} (${} / ${} per 1M input / output tokens)", model, price.input, price.output),
+        None => println!("🤖 Model: {} (no known price; add [ai.pricing.\"{}\"] to the config)", model, model),
+    }
     println!("🔤 Source Locale: {}", source_locale);
     println!("🌍 Target Locales: {}", config.app.target_locales.len());
     println!("💰 Max Cost (with retries): ${:.4}", estimated_cost * 1.5);
     
-    if estimated_cost > 5.0 {
+    if prices.price(model).is_none() {
+        // Nothing to judge without a price
+    } else if estimated_cost > 5.0 {
         println!("\n⚠️  High Cost Alert!");
         println!("   • Consider using gpt-4o-mini instead");
         println!("   • Reduce content length if possible");
@@ -111,4 +136,62 @@ pub async fn run(detailed: bool, config_path: &Path) -> Result<()> {
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Summarize the spend recorded in the cost ledger by `translate` and `template eval`
+pub async fn report(config_path: &Path, options: ReportOptions) -> Result<()> {
+    let config = Config::load(config_path)
+        .context("Failed to load configuration")?;
+    
+    let group = ReportGroup::parse(&options.by)?;
+    if let Some(month) = &options.month {
+        chrono::NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .with_context(|| format!("Invalid month '{}', expected YYYY-MM", month))?;
+    }
+    
+    let ledger = Ledger::open()?;
+    let entries: Vec<_> = ledger
+        .entries()?
+        .into_iter()
+        .filter(|entry| options.month.as_ref().is_none_or(|month| entry.month() == *month))
+        .collect();
+    let rows = ledger::summarize(&entries, group);
+    
+    match options.output_format.as_str() {
+        "json" => {
+            let rows: Vec<_> = rows
+                .iter()
+                .map(|row| json!({
+                    "key": row.key,
+                    "runs": row.runs,
+                    "inputTokens": row.input_tokens,
+                    "outputTokens": row.output_tokens,
+                    "cost": row.cost,
+                }))
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&rows)
+                .context("Failed to serialize cost report as JSON")?);
+            return Ok(());
+        }
+        "table" => {
+            print!("{}", DataFormatter::format_cost_report(&rows, group));
+        }
+        other => anyhow::bail!("Unsupported output format: {}", other),
+    }
+    
+    let unpriced: Vec<&str> = entries.iter().filter(|entry| !entry.priced).map(|entry| entry.model.as_str()).collect();
+    if !unpriced.is_empty() {
+        println!("ℹ️  {} run(s) used a model without a known price and are counted as $0", unpriced.len());
+    }
+    
+    let ai_config = config.ai.unwrap_or_default();
+    if let Some(budget) = ai_config.monthly_budget {
+        let spent = ledger.spent_this_month()?;
+        println!("📅 This month: ${:.4} of ${} budget spent (${:.4} left)", spent, budget, (budget - spent).max(0.0));
+    }
+    if let Some(limit) = ai_config.max_cost_per_run {
+        println!("🧾 Limit per translate run: ${}", limit);
+    }
+    
+    Ok(())
+}
@@ -1,28 +1,141 @@
+use crate::changes::{diff_locales, diff_screenshot_manifests, FieldChange, ScreenshotChange};
 use crate::config::Config;
+use crate::formatter::DataFormatter;
+use crate::workspace::{fetch_remote, Workspace, METADATA_FIELDS};
 use anyhow::{Context, Result};
+use serde_json::json;
+use std::collections::BTreeSet;
 use std::path::Path;
 
-pub async fn run(config_path: &Path) -> Result<()> {
-    println!("Comparing local and remote content...");
-    
-    let _config = Config::load(config_path)
+/// Pseudo-field selecting screenshot manifests in `--fields` (version diffs only)
+const SCREENSHOTS_FIELD: &str = "screenshots";
+
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    pub locales: Vec<String>,
+    pub fields: Vec<String>,
+    pub output_format: String,
+    /// Compare two downloaded version snapshots instead of local vs remote
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            locales: Vec::new(),
+            fields: Vec::new(),
+            output_format: "table".to_string(),
+            from: None,
+            to: None,
+        }
+    }
+}
+
+pub async fn run(config_path: &Path, options: DiffOptions) -> Result<()> {
+    let json_output = options.output_format == "json";
+    let config = Config::load(config_path)
         .context("Failed to load configuration")?;
-    
-    // TODO: Implement actual diff logic
-    println!("📊 Analyzing differences...");
-    
-    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-    
-    println!("🔍 Changes detected:");
-    println!("   📝 App description (en-US): Modified");
-    println!("   🖼️  Screenshots (zh-Hans): 2 added, 1 removed");
-    println!("   🏷️  Keywords (fr-FR): Updated");
-    println!("   ✨ What's new (de-DE): New content");
-    
-    println!("\n📈 Summary:");
-    println!("   • 4 locales with changes");
-    println!("   • 7 total modifications");
-    println!("   • Ready for push");
-    
+
+    let versions = match (&options.from, &options.to) {
+        (Some(from), Some(to)) => Some((from.clone(), to.clone())),
+        (None, None) => None,
+        _ => anyhow::bail!("--from and --to must be used together"),
+    };
+
+    for field in &options.fields {
+        let screenshots_allowed = versions.is_some() && field == SCREENSHOTS_FIELD;
+        if !METADATA_FIELDS.contains(&field.as_str()) && !screenshots_allowed {
+            anyhow::bail!("Unknown field '{}'. Expected one of: {}", field, METADATA_FIELDS.join(", "));
+        }
+    }
+
+    let workspace = Workspace::open(&config)?;
+
+    let (old, new, old_label, new_label) = match &versions {
+        Some((from, to)) => {
+            if !json_output {
+                println!("Comparing version {} with version {}...", from, to);
+            }
+            (workspace.read_version(from)?, workspace.read_version(to)?, from.clone(), to.clone())
+        }
+        None => {
+            if !json_output {
+                println!("Comparing local and remote content...");
+            }
+            let local = workspace.read_all()
+                .context("Failed to read local workspace")?;
+            if local.is_empty() {
+                anyhow::bail!(
+                    "No local metadata found in {}. Run 'rosetta-connect pull' first.",
+                    workspace.current_dir().display()
+                );
+            }
+
+            rc_node::init_node_runtime()
+                .context("Failed to initialize Node.js runtime")?;
+            let remote = fetch_remote(&config, None).await?;
+            (remote, local, "remote".to_string(), "local".to_string())
+        }
+    };
+
+    let locale_selected = |locale: &str| options.locales.is_empty() || options.locales.iter().any(|l| l == locale);
+    let field_selected = |field: &str| options.fields.is_empty() || options.fields.iter().any(|f| f == field);
+
+    let changes: Vec<FieldChange> = diff_locales(&old, &new)
+        .into_iter()
+        .filter(|c| locale_selected(&c.locale) && field_selected(&c.field))
+        .collect();
+
+    // Screenshot manifests only exist in version snapshots
+    let mut screenshot_changes: Vec<ScreenshotChange> = Vec::new();
+    if let Some((from, to)) = &versions {
+        if field_selected(SCREENSHOTS_FIELD) {
+            let locales: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for locale in locales.into_iter().filter(|l| locale_selected(l)) {
+                let old_manifest = workspace.read_screenshot_manifest(from, locale)?;
+                let new_manifest = workspace.read_screenshot_manifest(to, locale)?;
+                screenshot_changes.extend(diff_screenshot_manifests(locale, old_manifest.as_ref(), new_manifest.as_ref()));
+            }
+        }
+    }
+
+    let locale_count = changes
+        .iter()
+        .map(|c| c.locale.as_str())
+        .chain(screenshot_changes.iter().map(|c| c.locale.as_str()))
+        .collect::<BTreeSet<_>>()
+        .len();
+
+    match options.output_format.as_str() {
+        "json" => {
+            let output = json!({
+                "from": old_label,
+                "to": new_label,
+                "differences": changes,
+                "screenshots": screenshot_changes,
+                "summary": {
+                    "locales": locale_count,
+                    "fields": changes.len(),
+                    "screenshots": screenshot_changes.len(),
+                }
+            });
+            println!("{}", serde_json::to_string_pretty(&output)
+                .context("Failed to serialize diff as JSON")?);
+        }
+        "table" => {
+            print!("{}", DataFormatter::format_field_diff(&changes, &old_label, &new_label));
+            if versions.is_some() {
+                print!("{}", DataFormatter::format_screenshot_diff(&screenshot_changes));
+            }
+        }
+        other => anyhow::bail!("Unsupported output format: {}", other),
+    }
+
+    // Like `git diff --exit-code`, signal differences to scripts and CI
+    if !changes.is_empty() || !screenshot_changes.is_empty() {
+        std::process::exit(crate::EXIT_FOUND);
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
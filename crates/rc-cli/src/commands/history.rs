@@ -0,0 +1,56 @@
+use crate::config::Config;
+use crate::formatter::DataFormatter;
+use crate::history::HistoryStore;
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct HistoryOptions {
+    pub limit: Option<usize>,
+    pub output_format: String,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            limit: None,
+            output_format: "table".to_string(),
+        }
+    }
+}
+
+pub async fn run(config_path: &Path, options: HistoryOptions) -> Result<()> {
+    let config = Config::load(config_path)
+        .context("Failed to load configuration")?;
+
+    let mut snapshots = HistoryStore::open(&config)?.list()?;
+    if let Some(limit) = options.limit {
+        snapshots.truncate(limit);
+    }
+
+    match options.output_format.as_str() {
+        "json" => {
+            let entries: Vec<_> = snapshots
+                .iter()
+                .map(|s| json!({
+                    "id": s.id,
+                    "createdAt": s.created_at,
+                    "source": s.source,
+                    "version": s.version,
+                    "locales": s.locales.keys().collect::<Vec<_>>(),
+                    "fields": s.field_count(),
+                    "screenshotManifests": s.screenshots.len(),
+                }))
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&entries)
+                .context("Failed to serialize history as JSON")?);
+        }
+        "table" => {
+            print!("{}", DataFormatter::format_history(&snapshots));
+        }
+        other => anyhow::bail!("Unsupported output format: {}", other),
+    }
+
+    Ok(())
+}
@@ -25,6 +25,7 @@ pub async fn run(bundle_id: Option<String>, default_locale: String, config_path:
     }
     
     let config = Config::create_default(bundle_id.clone(), default_locale.clone());
+    config.validate()?;
     config.save(config_path)
         .context("Failed to save configuration file")?;
     
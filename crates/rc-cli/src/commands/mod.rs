@@ -6,6 +6,8 @@ pub mod diff;
 pub mod push;
 pub mod validate;
 pub mod preview;
+pub mod review;
 pub mod rollback;
+pub mod history;
 pub mod cost;
 pub mod template;
\ No newline at end of file
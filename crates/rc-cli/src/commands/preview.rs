@@ -1,42 +1,58 @@
 use crate::config::Config;
+use crate::formatter::DataFormatter;
+use crate::product_page;
+use crate::workspace::Workspace;
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-pub async fn run(locale: Option<String>, config_path: &Path) -> Result<()> {
+#[derive(Debug, Clone, Default)]
+pub struct PreviewOptions {
+    pub locale: Option<String>,
+    /// Simulate the App Store search card and product page instead of listing fields
+    pub render: bool,
+    /// Where to write the HTML simulation (default: preview-<locale>.html)
+    pub html_file: Option<PathBuf>,
+}
+
+pub async fn run(config_path: &Path, options: PreviewOptions) -> Result<()> {
     let config = Config::load(config_path)
         .context("Failed to load configuration")?;
     
-    let target_locale = locale.unwrap_or(config.app.default_locale.clone());
-    
-    println!("Previewing content for locale: {}", target_locale);
-    println!("{}", "=".repeat(50));
-    
-    // TODO: Load actual content from files
-    println!("📱 App Name: My Awesome App");
-    println!("📝 Subtitle: The best app for productivity");
-    
-    println!("\n📄 Description:");
-    println!("Transform your daily workflow with our innovative app...");
-    println!("(truncated - 4000 character limit)");
-    
-    println!("\n🏷️  Keywords:");
-    println!("productivity, workflow, efficiency, tools, business");
-    
-    println!("\n✨ What's New:");
-    println!("• Bug fixes and performance improvements");
-    println!("• New dark mode theme");
-    println!("• Enhanced user interface");
-    
-    println!("\n🖼️  Screenshots:");
-    println!("   1. Main interface (1284x2778)");
-    println!("   2. Settings screen (1284x2778)");
-    println!("   3. Dark mode (1284x2778)");
-    println!("   4. Feature showcase (1284x2778)");
-    
-    println!("\n📊 Content Stats:");
-    println!("   • Description: 1,234 / 4,000 characters");
-    println!("   • Keywords: 45 / 100 characters");
-    println!("   • Screenshots: 4 / 10 images");
-    
+    let target_locale = options.locale.unwrap_or(config.app.default_locale.clone());
+    
+    let workspace = Workspace::open(&config)?;
+    if !workspace.locales()?.contains(&target_locale) {
+        anyhow::bail!(
+            "No local metadata for locale {} in {}. Run 'rosetta-connect pull' or 'translate' first.",
+            target_locale,
+            workspace.current_dir().display()
+        );
+    }
+    
+    let fields = workspace.read_locale(&target_locale)?;
+    
+    // Screenshots come from the version snapshot the workspace was pulled from
+    let version = workspace.pulled_version()?;
+    let screenshots = match &version {
+        Some(version) => workspace.screenshots(version, &target_locale)?,
+        None => Vec::new(),
+    };
+    
+    if !options.render {
+        print!("{}", DataFormatter::format_preview(&target_locale, version.as_deref(), &fields, &screenshots));
+        return Ok(());
+    }
+    
+    let renders = product_page::render_all(&fields);
+    print!("{}", DataFormatter::format_product_page(&target_locale, &renders));
+    
+    let html_file = options.html_file
+        .unwrap_or_else(|| PathBuf::from(format!("preview-{}.html", target_locale)));
+    let html = product_page::to_html(&target_locale, version.as_deref(), &fields, &screenshots, &renders);
+    fs::write(&html_file, html)
+        .with_context(|| format!("Failed to write HTML preview: {}", html_file.display()))?;
+    
+    println!("\n🌐 HTML preview saved to: {}", html_file.display());
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,5 +1,10 @@
+use crate::changes::{diff_fields, FieldChange};
 use crate::config::Config;
 use crate::formatter::DataFormatter;
+use crate::history::{HistoryStore, Snapshot, SnapshotSource};
+use crate::memory::{MemoryOrigin, TranslationMemory};
+use crate::provenance::{record_baseline, stale_translations, untracked_translations};
+use crate::workspace::{fields_from_value, locales_from_download, Workspace};
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
@@ -12,6 +17,8 @@ use regex::Regex;
 #[derive(Debug, Clone)]
 pub struct PullOptions {
     pub force_refresh: bool,
+    /// Overwrite local changes that have not been pushed
+    pub force: bool,
     pub retry_count: u32,
     pub output_format: String,
     pub export_file: Option<PathBuf>,
@@ -22,6 +29,7 @@ impl Default for PullOptions {
     fn default() -> Self {
         Self {
             force_refresh: false,
+            force: false,
             retry_count: 3,
             output_format: "table".to_string(),
             export_file: None,
@@ -41,7 +49,7 @@ pub async fn run(config_path: &Path, options: PullOptions) -> Result<()> {
     if !options.force_refresh {
         if let Some(cached_data) = check_cache(&config).await? {
             println!("📋 Using cached data (use --force-refresh to update)");
-            display_results(&cached_data, &options)?;
+            display_results(&cached_data, &config, &options)?;
             return Ok(());
         }
     }
@@ -54,14 +62,32 @@ pub async fn run(config_path: &Path, options: PullOptions) -> Result<()> {
     let result = download_with_retry(&config, &options).await
         .context("Failed to download app data after retries")?;
     
+    // Unpushed edits and unreviewed drafts would be replaced by the download
+    let overwritten = local_changes(&result, &Workspace::open(&config)?)?;
+    if !overwritten.is_empty() {
+        println!("⚠️  Local changes that pull would overwrite:");
+        print!("{}", DataFormatter::format_field_diff(&overwritten, "last pull", "local"));
+        if !options.force {
+            anyhow::bail!(
+                "{} local change(s) have not been pushed. Push them first, or pull with --force to discard them.",
+                overwritten.len()
+            );
+        }
+        println!("🗑️  Discarding local changes (--force)");
+    }
+    
     // Step 5: Save to cache and persistent storage
     save_to_cache(&result, &config).await
         .context("Failed to save data to cache")?;
     save_to_files(&result, &config).await
         .context("Failed to save data to files")?;
+    record_snapshot(&result, &config)
+        .context("Failed to record snapshot")?;
+    update_memory(&result, &config)
+        .context("Failed to update translation memory")?;
     
     // Step 6: Display and export results
-    display_results(&result, &options)?;
+    display_results(&result, &config, &options)?;
     
     if let Some(export_path) = &options.export_file {
         export_data(&result, export_path, &options.output_format)
@@ -169,7 +195,7 @@ async fn download_with_retry(config: &Config, options: &PullOptions) -> Result<V
         
         let start_time = Instant::now();
         
-        match rc_node::asc_download(config.app.bundle_id.clone()).await {
+        match rc_node::asc_download(config.app.bundle_id.clone(), None).await {
             Ok(mut result) => {
                 let duration = start_time.elapsed();
                 main_pb.finish_with_message(format!(
@@ -246,38 +272,93 @@ async fn save_to_cache(data: &Value, config: &Config) -> Result<()> {
 }
 
 async fn save_to_files(data: &Value, config: &Config) -> Result<()> {
-    let data_dir = get_data_dir(config)?;
-    fs::create_dir_all(&data_dir)
-        .context("Failed to create data directory")?;
+    let workspace = Workspace::open(config)?;
+    
+    let source_locale = &config.app.default_locale;
+    let locales = locales_from_download(data);
+    let source = match locales.get(source_locale) {
+        Some(source) => Some(source.clone()),
+        None if workspace.locales()?.contains(source_locale) => Some(workspace.read_locale(source_locale)?),
+        None => None,
+    };
     
-    // Save metadata for each locale
-    if let Some(metadata) = data.get("metadata").and_then(|v| v.as_object()) {
-        for (locale, locale_data) in metadata {
-            let locale_dir = data_dir.join(locale);
-            fs::create_dir_all(&locale_dir)
-                .context(format!("Failed to create locale directory for {}", locale))?;
-            
-            let metadata_file = locale_dir.join("metadata.json");
-            let metadata_content = serde_json::to_string_pretty(locale_data)
-                .context("Failed to serialize locale metadata")?;
-            
-            fs::write(&metadata_file, metadata_content)
-                .context(format!("Failed to write metadata for locale {}", locale))?;
+    // Save metadata for each locale; per-field files of fields no longer on App Store Connect are removed.
+    // Pulled translations become the baseline that later source changes are checked against.
+    for (locale, fields) in &locales {
+        workspace.write_locale(locale, fields)?;
+        if let Some(source) = source.as_ref().filter(|_| locale != source_locale) {
+            let mut provenance = workspace.read_provenance(locale)?;
+            record_baseline(fields, &mut provenance, source, source_locale);
+            workspace.write_provenance(locale, &provenance)?;
         }
     }
     
     // Save summary data
-    let summary_file = data_dir.join("summary.json");
-    let summary_content = serde_json::to_string_pretty(data)
-        .context("Failed to serialize summary data")?;
-    
-    fs::write(&summary_file, summary_content)
-        .context("Failed to write summary file")?;
-    
+    workspace.write_summary(data)
+}
+
+/// Fields changed locally since the last pull that the download would replace with other text.
+/// Without a previous pull every local field counts, unless the download has the same text.
+pub(crate) fn local_changes(data: &Value, workspace: &Workspace) -> Result<Vec<FieldChange>> {
+    let local = workspace.read_all()?;
+    let pulled = workspace.read_summary()?.map(|summary| locales_from_download(&summary)).unwrap_or_default();
+    
+    let mut changes = Vec::new();
+    for (locale, incoming) in locales_from_download(data) {
+        let Some(local_fields) = local.get(&locale) else {
+            continue;
+        };
+        let base = pulled.get(&locale).cloned().unwrap_or_default();
+        changes.extend(diff_fields(&locale, &base, local_fields).into_iter().filter(|change| {
+            let incoming = incoming.get(&change.field).filter(|text| !text.trim().is_empty());
+            change.new.as_ref() != incoming
+        }));
+    }
+    Ok(changes)
+}
+
+fn record_snapshot(data: &Value, config: &Config) -> Result<()> {
+    let workspace = Workspace::open(config)?;
+    let version = data.get("appVersion").and_then(|v| v.as_str()).map(|v| v.to_string());
+    let locales = locales_from_download(data);
+    let screenshots = Snapshot::read_manifests(&workspace, version.as_deref(), &locales)?;
+
+    let snapshot = Snapshot::new(SnapshotSource::Pull, &config.app.bundle_id, version, locales, screenshots);
+    let pruned = HistoryStore::open(config)?.record(&snapshot)?;
+
+    println!("🗂️  Recorded snapshot {}", snapshot.id);
+    if !pruned.is_empty() {
+        println!("🧹 Pruned {} old snapshot(s)", pruned.len());
+    }
+    Ok(())
+}
+
+/// Published localizations are approved translations of the default locale
+fn update_memory(data: &Value, config: &Config) -> Result<()> {
+    let mut memory = TranslationMemory::open(config)?;
+    if !memory.enabled() {
+        return Ok(());
+    }
+
+    let source_locale = &config.app.default_locale;
+    let locales = locales_from_download(data);
+    let Some(source) = locales.get(source_locale) else {
+        return Ok(());
+    };
+
+    let added: usize = locales
+        .iter()
+        .filter(|(locale, _)| *locale != source_locale)
+        .map(|(locale, fields)| memory.add_fields(source_locale, locale, source, fields, MemoryOrigin::Pulled))
+        .sum();
+    if added > 0 {
+        memory.save()?;
+        println!("🧠 Added {} field(s) to the translation memory ({} entries)", added, memory.len());
+    }
     Ok(())
 }
 
-fn display_results(data: &Value, options: &PullOptions) -> Result<()> {
+fn display_results(data: &Value, config: &Config, options: &PullOptions) -> Result<()> {
     match options.output_format.as_str() {
         "json" => {
             let json_output = serde_json::to_string_pretty(data)
@@ -289,13 +370,21 @@ fn display_results(data: &Value, options: &PullOptions) -> Result<()> {
                 .context("Failed to format data as CSV")?;
             println!("{}", csv_output);
         }
-        "table" | _ => {
+        _ => {
             // Show default locale content
             let default_only = DataFormatter::format_default_locale(data);
             println!("{}", default_only);
             
-            // Show multi-locale compact status table
-            let locales_table = DataFormatter::format_locales_status_table(data);
+            // Show multi-locale compact status table, flagging translations of older source text
+            let source = data
+                .get("metadata")
+                .and_then(|m| m.get(&config.app.default_locale))
+                .map(fields_from_value)
+                .unwrap_or_default();
+            let workspace = Workspace::open(config)?;
+            let stale = stale_translations(&workspace, &config.app.default_locale, &source)?;
+            let untracked = untracked_translations(&workspace, &config.app.default_locale)?;
+            let locales_table = DataFormatter::format_locales_status_table(data, &stale, &untracked);
             println!("{}", locales_table);
         }
     }
@@ -361,11 +450,3 @@ fn get_cache_dir(config: &Config) -> Result<PathBuf> {
     cache_dir.push(&config.app.bundle_id);
     Ok(cache_dir)
 }
-
-fn get_data_dir(config: &Config) -> Result<PathBuf> {
-    let mut data_dir = std::env::current_dir()
-        .context("Failed to get current directory")?;
-    data_dir.push(&config.app.bundle_id);
-    data_dir.push("current");
-    Ok(data_dir)
-}
\ No newline at end of file
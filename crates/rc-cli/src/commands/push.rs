@@ -1,59 +1,360 @@
+use crate::changes::{diff_locales, ChangeKind, FieldChange};
 use crate::config::Config;
+use crate::formatter::DataFormatter;
+use crate::history::{HistoryStore, Snapshot, SnapshotSource};
+use crate::workspace::{fetch_remote, locale_hash, LocaleFields, Workspace};
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use indicatif::{ProgressBar, ProgressStyle};
-use std::path::Path;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
-pub async fn run(version: String, yes: bool, config_path: &Path) -> Result<()> {
-    println!("Preparing to push version {} to App Store Connect...", version);
-    
-    let _config = Config::load(config_path)
+const PLAN_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone)]
+pub struct PushOptions {
+    pub version: Option<String>,
+    pub yes: bool,
+    pub plan: bool,
+    pub plan_file: Option<PathBuf>,
+    pub apply: Option<PathBuf>,
+}
+
+/// A reviewed set of field changes, written by `push --plan` and executed by `push --apply`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushPlan {
+    pub format_version: u32,
+    pub app_id: String,
+    pub version: String,
+    pub created_at: DateTime<Utc>,
+    /// App Store version the local workspace was pulled from (`summary.json`)
+    pub pulled_version: Option<String>,
+    /// Hash of the remote fields of every planned locale at plan time
+    pub remote_hashes: BTreeMap<String, String>,
+    pub changes: Vec<FieldChange>,
+}
+
+pub async fn run(config_path: &Path, options: PushOptions) -> Result<()> {
+    let config = Config::load(config_path)
         .context("Failed to load configuration")?;
-    
-    if !yes {
-        println!("⚠️  This will upload content to App Store Connect. Continue? (y/N)");
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)
-            .context("Failed to read confirmation")?;
-        
-        if !input.trim().to_lowercase().starts_with('y') {
-            println!("❌ Upload cancelled");
-            return Ok(());
+
+    if let Some(plan_path) = &options.apply {
+        return apply_plan(&config, plan_path, options.yes).await;
+    }
+
+    let version = options.version.clone()
+        .context("A version is required unless --apply is given")?;
+
+    if options.plan {
+        let plan_path = options.plan_file.clone()
+            .unwrap_or_else(|| PathBuf::from(format!("rosetta-plan-{}.json", version)));
+        return write_plan(&config, &version, &plan_path).await;
+    }
+
+    push_workspace(&config, &version, options.yes).await
+}
+
+async fn push_workspace(config: &Config, version: &str, yes: bool) -> Result<()> {
+    println!("Preparing to push version {} to App Store Connect...", version);
+
+    let workspace = Workspace::open(config)?;
+    let local = read_local(&workspace)?;
+
+    if let Some(pulled_version) = workspace.pulled_version()? {
+        if pulled_version != version {
+            println!("⚠️  Local workspace was pulled from version {}, pushing to {}", pulled_version, version);
         }
     }
-    
-    let pb = ProgressBar::new(100);
+
+    let payloads = build_payloads(&local);
+    let field_count: usize = payloads.iter().map(|(_, fields)| fields.len()).sum();
+    println!("📦 {} locales, {} fields to upload", payloads.len(), field_count);
+
+    if !yes && !confirm_upload()? {
+        println!("❌ Upload cancelled");
+        return Ok(());
+    }
+
+    rc_node::init_node_runtime()
+        .context("Failed to initialize Node.js runtime")?;
+
+    let results = upload_payloads(&config.app.bundle_id, version, payloads).await?;
+    report_results(&results, version)?;
+
+    let snapshot = Snapshot::from_workspace(SnapshotSource::Push, config, &workspace, Some(version.to_string()))?;
+    record_snapshot(config, &snapshot)
+}
+
+async fn write_plan(config: &Config, version: &str, plan_path: &Path) -> Result<()> {
+    println!("📋 Planning push of version {}...", version);
+
+    let workspace = Workspace::open(config)?;
+    let local = read_local(&workspace)?;
+
+    rc_node::init_node_runtime()
+        .context("Failed to initialize Node.js runtime")?;
+
+    println!("📥 Fetching remote state of version {}...", version);
+    let remote = fetch_remote(config, Some(version)).await?;
+
+    // Push never clears remote values, so removals are not part of a plan
+    let changes: Vec<FieldChange> = diff_locales(&remote, &local)
+        .into_iter()
+        .filter(|change| change.kind != ChangeKind::Removed)
+        .collect();
+
+    if changes.is_empty() {
+        println!("✅ No changes. App Store Connect already matches the local workspace.");
+        return Ok(());
+    }
+
+    let remote_hashes = planned_locales(&changes)
+        .into_iter()
+        .map(|locale| {
+            let hash = locale_hash(remote.get(&locale).unwrap_or(&LocaleFields::new()));
+            (locale, hash)
+        })
+        .collect();
+
+    let plan = PushPlan {
+        format_version: PLAN_FORMAT_VERSION,
+        app_id: config.app.bundle_id.clone(),
+        version: version.to_string(),
+        created_at: Utc::now(),
+        pulled_version: workspace.pulled_version()?,
+        remote_hashes,
+        changes,
+    };
+
+    println!("{}", DataFormatter::format_push_plan(&plan.changes));
+
+    let content = serde_json::to_string_pretty(&plan)
+        .context("Failed to serialize push plan")?;
+    fs::write(plan_path, content)
+        .with_context(|| format!("Failed to write plan file: {}", plan_path.display()))?;
+
+    println!("📁 Plan saved to: {}", plan_path.display());
+    println!("💡 Apply it with: rosetta-connect push --apply {}", plan_path.display());
+    Ok(())
+}
+
+async fn apply_plan(config: &Config, plan_path: &Path, yes: bool) -> Result<()> {
+    let content = fs::read_to_string(plan_path)
+        .with_context(|| format!("Failed to read plan file: {}", plan_path.display()))?;
+    let plan: PushPlan = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse plan file: {}", plan_path.display()))?;
+
+    if plan.format_version != PLAN_FORMAT_VERSION {
+        anyhow::bail!("Unsupported plan format version {} (expected {})", plan.format_version, PLAN_FORMAT_VERSION);
+    }
+    if plan.app_id != config.app.bundle_id {
+        anyhow::bail!("Plan was created for {}, but the configured app is {}", plan.app_id, config.app.bundle_id);
+    }
+
+    println!("Applying plan for version {} (created {})...", plan.version, plan.created_at.format("%Y-%m-%d %H:%M UTC"));
+    println!("{}", DataFormatter::format_push_plan(&plan.changes));
+
+    rc_node::init_node_runtime()
+        .context("Failed to initialize Node.js runtime")?;
+
+    println!("🔍 Checking remote state for drift...");
+    // Compare against the version the plan was made for, not whichever version is live
+    let remote = fetch_remote(config, Some(&plan.version)).await?;
+    let drifted: Vec<&String> = plan
+        .remote_hashes
+        .iter()
+        .filter(|(locale, hash)| locale_hash(remote.get(*locale).unwrap_or(&LocaleFields::new())) != **hash)
+        .map(|(locale, _)| locale)
+        .collect();
+
+    if !drifted.is_empty() {
+        let locales: Vec<&str> = drifted.iter().map(|l| l.as_str()).collect();
+        anyhow::bail!(
+            "Remote state changed since the plan was created ({}). Run 'rosetta-connect push {} --plan' again.",
+            locales.join(", "),
+            plan.version
+        );
+    }
+    println!("✅ Remote state unchanged since plan");
+
+    if !yes && !confirm_upload()? {
+        println!("❌ Upload cancelled");
+        return Ok(());
+    }
+
+    let mut by_locale: BTreeMap<String, LocaleFields> = BTreeMap::new();
+    for change in &plan.changes {
+        if let Some(value) = &change.new {
+            by_locale
+                .entry(change.locale.clone())
+                .or_default()
+                .insert(change.field.clone(), value.clone());
+        }
+    }
+
+    let results = upload_payloads(&config.app.bundle_id, &plan.version, by_locale.clone().into_iter().collect()).await?;
+    report_results(&results, &plan.version)?;
+
+    // The remote now holds its previous content with the planned fields applied
+    let mut pushed = remote;
+    for (locale, fields) in by_locale {
+        pushed.entry(locale).or_default().extend(fields);
+    }
+    let workspace = Workspace::open(config)?;
+    let screenshots = Snapshot::read_manifests(&workspace, plan.pulled_version.as_deref(), &pushed)?;
+    let snapshot = Snapshot::new(SnapshotSource::Push, &config.app.bundle_id, Some(plan.version.clone()), pushed, screenshots);
+    record_snapshot(config, &snapshot)
+}
+
+fn record_snapshot(config: &Config, snapshot: &Snapshot) -> Result<()> {
+    let pruned = HistoryStore::open(config)?.record(snapshot)
+        .context("Failed to record snapshot")?;
+    println!("🗂️  Recorded snapshot {}", snapshot.id);
+    if !pruned.is_empty() {
+        println!("🧹 Pruned {} old snapshot(s)", pruned.len());
+    }
+    Ok(())
+}
+
+fn read_local(workspace: &Workspace) -> Result<BTreeMap<String, LocaleFields>> {
+    let local = workspace.read_all()
+        .context("Failed to read local workspace")?;
+
+    if local.is_empty() {
+        anyhow::bail!(
+            "No local metadata found in {}. Run 'rosetta-connect pull' first.",
+            workspace.current_dir().display()
+        );
+    }
+    Ok(local)
+}
+
+fn planned_locales(changes: &[FieldChange]) -> Vec<String> {
+    let mut locales: Vec<String> = changes.iter().map(|c| c.locale.clone()).collect();
+    locales.dedup();
+    locales
+}
+
+fn confirm_upload() -> Result<bool> {
+    println!("⚠️  This will upload content to App Store Connect. Continue? (y/N)");
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)
+        .context("Failed to read confirmation")?;
+    Ok(input.trim().to_lowercase().starts_with('y'))
+}
+
+fn report_results(results: &[Value], version: &str) -> Result<()> {
+    println!("{}", DataFormatter::format_push_results(results));
+
+    let failed = count_fields(results, false);
+    let succeeded = count_fields(results, true);
+
+    if failed > 0 {
+        anyhow::bail!("Push finished with {} failed field(s) ({} succeeded)", failed, succeeded);
+    }
+
+    println!("✅ Successfully pushed version {} to App Store Connect", version);
+    println!("📱 Updated {} fields across {} locales", succeeded, results.len());
+
+    Ok(())
+}
+
+/// Build one upload payload per locale, skipping empty fields so they never clear remote values
+fn build_payloads(local: &BTreeMap<String, LocaleFields>) -> Vec<(String, LocaleFields)> {
+    local
+        .iter()
+        .map(|(locale, fields)| {
+            let non_empty: LocaleFields = fields
+                .iter()
+                .filter(|(_, value)| !value.trim().is_empty())
+                .map(|(field, value)| (field.clone(), value.clone()))
+                .collect();
+            (locale.clone(), non_empty)
+        })
+        .filter(|(_, fields)| !fields.is_empty())
+        .collect()
+}
+
+/// Upload each locale's fields and collect per-field results:
+/// `[{ "locale": .., "fields": [{ "field": .., "success": .., "error": .. }] }]`
+async fn upload_payloads(
+    bundle_id: &str,
+    version: &str,
+    payloads: Vec<(String, LocaleFields)>,
+) -> Result<Vec<Value>> {
+    let pb = ProgressBar::new(payloads.len() as u64);
     pb.set_style(
         ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos:>7}/{len:7} {msg}")
-            .unwrap()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos:>3}/{len:3} {msg}")
+            .context("Failed to create progress bar style")?
             .progress_chars("##-"),
     );
-    
-    pb.set_message("Uploading metadata...");
-    for i in 0..40 {
-        pb.set_position(i);
-        tokio::time::sleep(Duration::from_millis(50)).await;
-    }
-    
-    pb.set_message("Uploading screenshots...");
-    for i in 40..80 {
-        pb.set_position(i);
-        tokio::time::sleep(Duration::from_millis(100)).await;
-    }
-    
-    pb.set_message("Finalizing...");
-    for i in 80..100 {
-        pb.set_position(i);
-        tokio::time::sleep(Duration::from_millis(50)).await;
-    }
-    
-    pb.finish_with_message("Upload completed!");
-    
-    println!("✅ Successfully pushed version {} to App Store Connect", version);
-    println!("📱 Updated 4 locales");
-    println!("🖼️  Uploaded 12 screenshots");
-    println!("⏱️  Total time: 8.5 seconds");
-    
-    Ok(())
-}
\ No newline at end of file
+
+    let start_time = Instant::now();
+    let mut results = Vec::new();
+
+    for (locale, fields) in payloads {
+        pb.set_message(format!("Uploading {}...", locale));
+
+        let mut payload = json!({
+            "appId": bundle_id,
+            "version": version,
+            "locale": locale,
+        });
+        for (field, value) in &fields {
+            payload[field] = json!(value);
+        }
+
+        let field_results = match rc_node::asc_upload(payload).await {
+            Ok(response) => parse_field_results(&response, &fields),
+            Err(e) => fields
+                .keys()
+                .map(|field| json!({ "field": field, "success": false, "error": e.to_string() }))
+                .collect(),
+        };
+
+        results.push(json!({ "locale": locale, "fields": field_results }));
+        pb.inc(1);
+    }
+
+    pb.finish_with_message(format!("Upload finished ({:.1}s)", start_time.elapsed().as_secs_f32()));
+    Ok(results)
+}
+
+/// Map the bridge response onto the fields we sent; older bridges only report overall success
+fn parse_field_results(response: &Value, fields: &LocaleFields) -> Vec<Value> {
+    if let Some(reported) = response.get("fields").and_then(|v| v.as_array()) {
+        return reported.clone();
+    }
+
+    let success = response.get("success").and_then(|v| v.as_bool()).unwrap_or(false);
+    let error = response
+        .get("errors")
+        .and_then(|v| v.as_array())
+        .map(|errors| errors.iter().filter_map(|e| e.as_str()).collect::<Vec<_>>().join("; "))
+        .or_else(|| response.get("message").and_then(|v| v.as_str()).map(|s| s.to_string()));
+
+    fields
+        .keys()
+        .map(|field| {
+            if success {
+                json!({ "field": field, "success": true })
+            } else {
+                json!({ "field": field, "success": false, "error": error })
+            }
+        })
+        .collect()
+}
+
+fn count_fields(results: &[Value], success: bool) -> usize {
+    results
+        .iter()
+        .filter_map(|r| r.get("fields").and_then(|f| f.as_array()))
+        .flatten()
+        .filter(|f| f.get("success").and_then(|v| v.as_bool()).unwrap_or(false) == success)
+        .count()
+}
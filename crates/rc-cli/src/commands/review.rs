@@ -0,0 +1,84 @@
+use crate::config::Config;
+use crate::locales;
+use crate::memory::{MemoryOrigin, TranslationMemory};
+use crate::provenance::mark_reviewed;
+use crate::workspace::{Workspace, METADATA_FIELDS};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default)]
+pub struct ReviewOptions {
+    /// Locales to mark; empty means every target locale in the workspace
+    pub locales: Vec<String>,
+    /// Fields to mark; empty means every non-empty field
+    pub fields: Vec<String>,
+}
+
+pub async fn run(config_path: &Path, options: ReviewOptions) -> Result<()> {
+    let config = Config::load(config_path)
+        .context("Failed to load configuration")?;
+
+    for field in &options.fields {
+        if !METADATA_FIELDS.contains(&field.as_str()) {
+            anyhow::bail!("Unknown field '{}'. Expected one of: {}", field, METADATA_FIELDS.join(", "));
+        }
+    }
+
+    let workspace = Workspace::open(&config)?;
+    let local_locales = workspace.locales()?;
+    let source_locale = &config.app.default_locale;
+    if !local_locales.contains(source_locale) {
+        anyhow::bail!(
+            "No local {} metadata in {}. Run 'rosetta-connect pull' first.",
+            source_locale,
+            workspace.current_dir().display()
+        );
+    }
+    let source = workspace.read_locale(source_locale)?;
+
+    let target_locales: Vec<String> = if options.locales.is_empty() {
+        config.app.target_locales
+            .iter()
+            .filter(|locale| local_locales.contains(locale))
+            .cloned()
+            .collect()
+    } else {
+        for locale in &options.locales {
+            if !local_locales.contains(locale) {
+                anyhow::bail!("No local metadata for locale {}", locale);
+            }
+        }
+        options.locales
+    };
+
+    let mut memory = TranslationMemory::open(&config)?;
+    let mut remembered = 0;
+    for locale in target_locales.iter().filter(|locale| *locale != source_locale) {
+        let fields = workspace.read_locale(locale)?;
+        let mut provenance = workspace.read_provenance(locale)?;
+
+        let marked = mark_reviewed(&fields, &mut provenance, &source, source_locale, &options.fields);
+        if marked.is_empty() {
+            println!("   {} {:<8} nothing to mark", locales::flag(locale), locale);
+            continue;
+        }
+
+        workspace.write_provenance(locale, &provenance)?;
+        if memory.enabled() {
+            let approved = fields
+                .iter()
+                .filter(|(field, _)| marked.contains(field))
+                .map(|(field, text)| (field.clone(), text.clone()))
+                .collect();
+            remembered += memory.add_fields(source_locale, locale, &source, &approved, MemoryOrigin::Reviewed);
+        }
+        println!("✅ {} {:<8} marked as reviewed: {}", locales::flag(locale), locale, marked.join(", "));
+    }
+
+    if remembered > 0 {
+        memory.save()?;
+        println!("🧠 Added {} reviewed field(s) to the translation memory", remembered);
+    }
+    println!("ℹ️  Reviewed fields are no longer reported as stale and won't be replaced by 'translate' without --overwrite");
+    Ok(())
+}
@@ -1,37 +1,233 @@
+use crate::changes::diff_locales;
+use crate::commands::push::{self, PushOptions};
 use crate::config::Config;
+use crate::formatter::DataFormatter;
+use crate::history::{HistoryStore, Snapshot, SnapshotSource};
+use crate::workspace::{LocaleFields, Workspace};
 use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::path::Path;
 
-pub async fn run(version: String, config_path: &Path) -> Result<()> {
-    println!("Rolling back to version {}...", version);
-    
-    let _config = Config::load(config_path)
+#[derive(Debug, Clone)]
+pub struct RollbackOptions {
+    /// Snapshot ID, unique ID prefix or App Store version
+    pub target: String,
+    /// Push the restored workspace to this App Store version afterwards
+    pub push_version: Option<String>,
+    pub yes: bool,
+}
+
+pub async fn run(config_path: &Path, options: RollbackOptions) -> Result<()> {
+    let config = Config::load(config_path)
         .context("Failed to load configuration")?;
-    
-    // TODO: Implement actual rollback logic
-    println!("🔍 Checking version history...");
-    
-    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-    
-    println!("📋 Available versions:");
-    println!("   • 2.3.4 (current)");
-    println!("   • 2.3.3");
-    println!("   • 2.3.2");
-    println!("   • 2.3.1");
-    
-    if version == "2.3.4" {
-        println!("⚠️  Already at version {}", version);
+
+    let store = HistoryStore::open(&config)?;
+    let snapshot = store.find(&options.target)?;
+
+    println!(
+        "Rolling back to snapshot {} ({}, {}, taken {})...",
+        snapshot.id,
+        snapshot.version.as_deref().unwrap_or("unknown version"),
+        snapshot.source.label(),
+        snapshot.created_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    let workspace = Workspace::open(&config)?;
+    let current = workspace.read_all()
+        .context("Failed to read local workspace")?;
+
+    // Only the locales in the snapshot are restored; others are left as they are
+    let restored_current: BTreeMap<String, LocaleFields> = current
+        .iter()
+        .filter(|(locale, _)| snapshot.locales.contains_key(*locale))
+        .map(|(locale, fields)| (locale.clone(), fields.clone()))
+        .collect();
+    let changes = diff_locales(&restored_current, &snapshot.locales);
+
+    if changes.is_empty() {
+        println!("✅ Local workspace already matches snapshot {}", snapshot.id);
+    } else {
+        print!("{}", DataFormatter::format_field_diff(&changes, "local", &snapshot.id));
+
+        if !options.yes && !confirm_restore()? {
+            println!("❌ Rollback cancelled");
+            return Ok(());
+        }
+
+        // Keep the state being overwritten so the rollback itself can be undone. Retention is
+        // applied only after restoring, since it may delete the snapshot being restored.
+        if !current.is_empty() {
+            let backup = Snapshot::from_workspace(SnapshotSource::Rollback, &config, &workspace, workspace.pulled_version()?)?;
+            store.write(&backup)?;
+            println!("🗂️  Saved current workspace as snapshot {}", backup.id);
+        }
+
+        restore(&workspace, &snapshot, &current)?;
+        println!("✅ Restored {} locale(s) from snapshot {}", snapshot.locales.len(), snapshot.id);
+
+        let pruned = store.prune()?;
+        if !pruned.is_empty() {
+            println!("🧹 Pruned {} old snapshot(s)", pruned.len());
+        }
+    }
+
+    let untouched: Vec<&str> = current
+        .keys()
+        .filter(|locale| !snapshot.locales.contains_key(*locale))
+        .map(|locale| locale.as_str())
+        .collect();
+    if !untouched.is_empty() {
+        println!("ℹ️  Not in snapshot, left unchanged: {}", untouched.join(", "));
+    }
+
+    match options.push_version {
+        Some(version) => {
+            push::run(config_path, PushOptions {
+                version: Some(version),
+                yes: options.yes,
+                plan: false,
+                plan_file: None,
+                apply: None,
+            }).await
+        }
+        None => {
+            println!("⚠️  Note: This only affects local files. Use 'push' to update App Store Connect");
+            Ok(())
+        }
+    }
+}
+
+/// Write a snapshot's locales and screenshot manifests back into the workspace over `current`
+fn restore(workspace: &Workspace, snapshot: &Snapshot, current: &BTreeMap<String, LocaleFields>) -> Result<()> {
+    for (locale, fields) in &snapshot.locales {
+        workspace.write_locale(locale, fields)?;
+
+        // Provenance describes the text being replaced; restored text counts as written by hand
+        let before = current.get(locale).cloned().unwrap_or_default();
+        let mut provenance = workspace.read_provenance(locale)?;
+        let recorded = provenance.len();
+        provenance.retain(|field, _| field_text(&before, field) == field_text(fields, field));
+        if provenance.len() != recorded {
+            workspace.write_provenance(locale, &provenance)?;
+        }
+    }
+
+    if let Some(version) = &snapshot.version {
+        for (locale, manifest) in &snapshot.screenshots {
+            workspace.write_screenshot_manifest(version, locale, manifest)?;
+        }
+    }
+
+    // Point summary.json at the restored version so push and validate use it. Its metadata is
+    // what the last pull downloaded and stays as it is, so pull still treats the restored text
+    // as unpushed local changes.
+    let Some(version) = &snapshot.version else {
         return Ok(());
+    };
+    let mut summary = workspace.read_summary()?.unwrap_or_else(|| json!({}));
+    if let Some(summary) = summary.as_object_mut() {
+        summary.insert("appVersion".to_string(), Value::String(version.clone()));
+    }
+    workspace.write_summary(&summary)
+}
+
+fn field_text<'a>(fields: &'a LocaleFields, field: &str) -> Option<&'a str> {
+    fields.get(field).map(|text| text.trim()).filter(|text| !text.is_empty())
+}
+
+fn confirm_restore() -> Result<bool> {
+    println!("⚠️  This will overwrite the local workspace. Continue? (y/N)");
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)
+        .context("Failed to read confirmation")?;
+    Ok(input.trim().to_lowercase().starts_with('y'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::pull::local_changes;
+    use crate::provenance::{FieldProvenance, ReviewStatus};
+    use crate::workspace::{content_hash, locales_from_download};
+    use chrono::Utc;
+
+    fn fields(pairs: &[(&str, &str)]) -> LocaleFields {
+        pairs.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect()
+    }
+
+    fn draft(text: &str) -> FieldProvenance {
+        FieldProvenance {
+            status: ReviewStatus::Draft,
+            model: Some("gpt-4o-mini".to_string()),
+            translated_at: Utc::now(),
+            reviewed_at: None,
+            source_locale: "en-US".to_string(),
+            source_hash: content_hash("Source"),
+            text_hash: content_hash(text),
+        }
+    }
+
+    #[test]
+    fn restore_keeps_the_pull_baseline_and_forgets_replaced_provenance() {
+        let root = std::env::temp_dir().join(format!("rosetta-rollback-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let workspace = Workspace::at(root.clone());
+
+        let pulled = fields(&[("name", "Heure"), ("subtitle", "Montre")]);
+        workspace.write_locale("fr-FR", &pulled).unwrap();
+        workspace.write_provenance("fr-FR", &BTreeMap::from([
+            ("name".to_string(), draft("Heure")),
+            ("subtitle".to_string(), draft("Montre")),
+        ])).unwrap();
+        workspace.write_summary(&json!({ "appVersion": "1.1", "metadata": { "fr-FR": pulled } })).unwrap();
+
+        let snapshot = Snapshot::new(
+            SnapshotSource::Pull,
+            "com.example.app",
+            Some("1.0".to_string()),
+            BTreeMap::from([("fr-FR".to_string(), fields(&[("name", "Horloge"), ("subtitle", "Montre")]))]),
+            BTreeMap::new(),
+        );
+        restore(&workspace, &snapshot, &workspace.read_all().unwrap()).unwrap();
+
+        assert_eq!(workspace.read_locale("fr-FR").unwrap()["name"], "Horloge");
+        let provenance = workspace.read_provenance("fr-FR").unwrap();
+        assert_eq!(provenance.keys().collect::<Vec<_>>(), ["subtitle"]);
+
+        let summary = workspace.read_summary().unwrap().unwrap();
+        assert_eq!(summary["appVersion"], "1.0");
+        assert_eq!(locales_from_download(&summary)["fr-FR"], pulled);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn pull_after_rollback_reports_the_restored_fields() {
+        let root = std::env::temp_dir().join(format!("rosetta-rollback-pull-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let workspace = Workspace::at(root.clone());
+
+        let download = json!({ "appVersion": "1.1", "metadata": { "fr-FR": { "name": "Heure", "subtitle": "Montre" } } });
+        workspace.write_locale("fr-FR", &locales_from_download(&download)["fr-FR"]).unwrap();
+        workspace.write_summary(&download).unwrap();
+
+        let snapshot = Snapshot::new(
+            SnapshotSource::Pull,
+            "com.example.app",
+            Some("1.0".to_string()),
+            BTreeMap::from([("fr-FR".to_string(), fields(&[("name", "Horloge"), ("subtitle", "Montre")]))]),
+            BTreeMap::new(),
+        );
+        restore(&workspace, &snapshot, &workspace.read_all().unwrap()).unwrap();
+
+        let changes = local_changes(&download, &workspace).unwrap();
+        let summary: Vec<(&str, &str, Option<&str>)> = changes
+            .iter()
+            .map(|c| (c.locale.as_str(), c.field.as_str(), c.new.as_deref()))
+            .collect();
+        assert_eq!(summary, [("fr-FR", "name", Some("Horloge"))]);
+
+        std::fs::remove_dir_all(&root).unwrap();
     }
-    
-    println!("🔄 Rolling back from 2.3.4 to {}...", version);
-    
-    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-    
-    println!("✅ Rollback completed successfully");
-    println!("📱 Restored metadata for all locales");
-    println!("🖼️  Restored screenshot sets");
-    println!("⚠️  Note: This only affects local files. Use 'push' to update App Store Connect");
-    
-    Ok(())
-}
\ No newline at end of file
+}
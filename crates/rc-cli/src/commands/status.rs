@@ -1,4 +1,7 @@
 use crate::config::Config;
+use crate::formatter::DataFormatter;
+use crate::provenance::{stale_translations, untracked_translations};
+use crate::workspace::Workspace;
 use anyhow::{Context, Result};
 use std::path::Path;
 use indicatif::{ProgressBar, ProgressStyle};
@@ -30,6 +33,9 @@ pub async fn run(config_path: &Path, all_versions: bool, detailed: bool) -> Resu
     // Display version status
     display_version_status(&version_info, all_versions, detailed)?;
     
+    // Local translations made from source text that has changed since
+    display_stale_translations(&config)?;
+    
     // Provide workflow recommendations
     provide_workflow_recommendations(&version_info)?;
     
@@ -118,6 +124,20 @@ fn display_single_version(version: &serde_json::Map<String, Value>, is_current:
     Ok(())
 }
 
+fn display_stale_translations(config: &Config) -> Result<()> {
+    let workspace = Workspace::open(config)?;
+    let source_locale = &config.app.default_locale;
+    if !workspace.locales()?.contains(source_locale) {
+        return Ok(());
+    }
+    
+    let source = workspace.read_locale(source_locale)?;
+    let stale = stale_translations(&workspace, source_locale, &source)?;
+    let untracked = untracked_translations(&workspace, source_locale)?;
+    print!("{}", DataFormatter::format_stale_translations(&stale, &untracked, source_locale));
+    Ok(())
+}
+
 fn provide_workflow_recommendations(version_info: &Value) -> Result<()> {
     println!("\n💡 {} Workflow Recommendations", "Smart".bold());
     println!("{}", "━".repeat(40));
@@ -130,9 +150,9 @@ fn provide_workflow_recommendations(version_info: &Value) -> Result<()> {
             "PREPARE_FOR_SUBMISSION" => {
                 println!("🎯 {} Perfect! Version {} is ready for localization work.", "Excellent:".green().bold(), version_string.bright_yellow());
                 println!("   📋 Recommended workflow:");
-                println!("   1️⃣  {} {}", "rosetta-connect pull".bright_cyan(), "# Get current content");
-                println!("   2️⃣  {} {}", "rosetta-connect translate --locales <your-locales>".bright_cyan(), "# Generate translations");
-                println!("   3️⃣  {} {}", "rosetta-connect push".bright_cyan(), "# Upload when ready");
+                println!("   1️⃣  {} # Get current content", "rosetta-connect pull".bright_cyan());
+                println!("   2️⃣  {} # Generate translations", "rosetta-connect translate --locales <your-locales>".bright_cyan());
+                println!("   3️⃣  {} # Upload when ready", "rosetta-connect push".bright_cyan());
             },
             
             "DEVELOPER_REJECTED" | "METADATA_REJECTED" | "REJECTED" => {
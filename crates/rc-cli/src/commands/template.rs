@@ -1,57 +1,344 @@
-use crate::config::Config;
+use crate::commands::translate::{self, AiSession};
+use crate::config::{AiConfig, Config};
+use crate::evaluation::{EvalFixtures, EvalOutput, EvalScores};
+use crate::formatter::DataFormatter;
+use crate::glossary;
+use crate::ledger::{Ledger, RunSpend};
+use crate::locales;
+use crate::memory::{MemoryLookup, TranslationMemory};
+use crate::provider::pricing::PriceTable;
+use crate::provider::tokens::TokenCounter;
+use crate::provider::{self, build_prompt, TokenUsage, TranslationRequest};
+use crate::templates::{self, TemplateOrigin, TemplateResolver, TemplateStore, VARIABLES};
+use crate::workspace::{read_source, LocaleFields, METADATA_FIELDS};
 use crate::TemplateAction;
 use anyhow::{Context, Result};
-use std::path::Path;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 pub async fn run(action: TemplateAction, config_path: &Path) -> Result<()> {
-    let _config = Config::load(config_path)
+    let config = Config::load(config_path)
         .context("Failed to load configuration")?;
+    let store = TemplateStore::open()?;
     
     match action {
         TemplateAction::List => {
             println!("📋 Available AI prompt templates:");
-            println!("   • app-description    - Generate app descriptions");
-            println!("   • keywords          - Generate relevant keywords");
-            println!("   • whats-new         - Generate release notes");
-            println!("   • marketing-copy    - Generate marketing text");
-            println!("   • privacy-policy    - Generate privacy policy excerpts");
+            for template in store.list()? {
+                let origin = match template.origin {
+                    TemplateOrigin::Builtin => "built-in",
+                    TemplateOrigin::Project => "project",
+                    TemplateOrigin::Override => "project, replaces built-in",
+                };
+                println!("   • {:<16} {:<28} {}", template.name, origin, template.description().unwrap_or(""));
+            }
+            let variables: Vec<String> = VARIABLES.iter().map(|name| format!("{{{{{}}}}}", name)).collect();
+            println!("\n💡 Variables: {}", variables.join(" "));
         }
-        
+    
+        TemplateAction::Show { name, resolved, locale, field } => {
+            match (resolved, locale, field) {
+                (true, Some(locale), Some(field)) => {
+                    show_resolved(&config, &store, name.as_deref(), &locale, &field).await?;
+                }
+                _ => {
+                    let name = name.context("Give a template name, or use --resolved with --locale and --field")?;
+                    let template = store.get(&name)?;
+                    println!("{}", template.body.trim_end());
+                }
+            }
+        }
+    
+        TemplateAction::Eval { name, fixtures, locales, model, detailed } => {
+            evaluate(&config, &store, &name, EvalOptions { fixtures, locales, model, detailed }).await?;
+        }
+    
         TemplateAction::Create { name, file } => {
             println!("Creating template '{}' from file: {}", name, file.display());
-            
+    
             if !file.exists() {
                 anyhow::bail!("Template file does not exist: {}", file.display());
             }
-            
-            // TODO: Implement template storage
-            println!("✅ Template '{}' created successfully", name);
+            templates::validate_name(&name)?;
+            if store.is_saved(&name) {
+                anyhow::bail!("Template '{}' already exists; change it with 'rosetta-connect template edit {}'", name, name);
+            }
+    
+            let body = fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read template file: {}", file.display()))?;
+            let path = store.save(&name, &body)
+                .with_context(|| format!("Template '{}' was not created", name))?;
+            if templates::builtin(&name).is_some() {
+                println!("ℹ️  Replaces the built-in template '{}'; delete it to use the built-in one again", name);
+            }
+            println!("✅ Template '{}' created: {}", name, path.display());
         }
-        
+    
         TemplateAction::Edit { name } => {
             println!("Editing template '{}'...", name);
-            
-            // TODO: Implement template editing (launch editor)
+    
+            // Edit a scratch copy so an invalid template never replaces a working one
+            let current = store.get(&name)?;
+            let scratch = std::env::temp_dir().join(format!("rosetta-template-{}.txt", name));
+            fs::write(&scratch, &current.body)
+                .with_context(|| format!("Failed to write {}", scratch.display()))?;
+    
             println!("📝 Opening template in editor...");
-            println!("✅ Template '{}' updated", name);
+            open_editor(&scratch)?;
+    
+            let edited = fs::read_to_string(&scratch)
+                .with_context(|| format!("Failed to read {}", scratch.display()))?;
+            if edited == current.body {
+                let _ = fs::remove_file(&scratch);
+                println!("✅ No changes to template '{}'", name);
+                return Ok(());
+            }
+    
+            let path = store.save(&name, &edited)
+                .with_context(|| format!("Template '{}' was not saved; your edit is in {}", name, scratch.display()))?;
+            let _ = fs::remove_file(&scratch);
+            if current.origin == TemplateOrigin::Builtin {
+                println!("ℹ️  Saved as a project template replacing the built-in '{}'", name);
+            }
+            println!("✅ Template '{}' updated: {}", name, path.display());
         }
-        
-        TemplateAction::Delete { name } => {
+    
+        TemplateAction::Delete { name, yes } => {
             println!("Deleting template '{}'...", name);
-            
-            // TODO: Implement template deletion with confirmation
-            println!("⚠️  Are you sure you want to delete template '{}'? (y/N)", name);
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)
-                .context("Failed to read confirmation")?;
-            
-            if input.trim().to_lowercase().starts_with('y') {
-                println!("✅ Template '{}' deleted", name);
-            } else {
-                println!("❌ Deletion cancelled");
+    
+            templates::validate_name(&name)?;
+            if !store.is_saved(&name) {
+                if templates::builtin(&name).is_some() {
+                    anyhow::bail!("'{}' is a built-in template and can't be deleted", name);
+                }
+                anyhow::bail!("Template '{}' not found", name);
+            }
+    
+            if !yes {
+                println!("⚠️  Are you sure you want to delete template '{}'? (y/N)", name);
+                let mut input = String::new();
+                std::io::stdin().read_line(&mut input)
+                    .context("Failed to read confirmation")?;
+                if !input.trim().to_lowercase().starts_with('y') {
+                    println!("❌ Deletion cancelled");
+                    return Ok(());
+                }
+            }
+    
+            store.remove(&name)?;
+            println!("✅ Template '{}' deleted", name);
+            if templates::builtin(&name).is_some() {
+                println!("ℹ️  The built-in template '{}' is used again", name);
             }
         }
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Print the prompt `translate` would send for one field of one locale, with the template the
+/// configuration chooses (or `name`), the local source text and translation memory references
+async fn show_resolved(config: &Config, store: &TemplateStore, name: Option<&str>, locale: &str, field: &str) -> Result<()> {
+    locales::validate_code(locale)?;
+    if !METADATA_FIELDS.contains(&field) {
+        anyhow::bail!("Unknown field '{}', expected one of: {}", field, METADATA_FIELDS.join(", "));
+    }
+    
+    let default_ai = AiConfig::default();
+    let ai_config = config.ai.as_ref().unwrap_or(&default_ai);
+    let resolver = TemplateResolver::new(store, ai_config, name)?;
+    let (template, chosen_by) = resolver.choose(locale, field);
+    
+    let source_locale = &config.app.default_locale;
+    let source_fields = read_source(config, false).await?;
+    let mut request = translate::request_for(config, &source_fields, locale, field, template);
+    if request.text.trim().is_empty() {
+        println!("ℹ️  The {} source text has no {}; translate would skip this field", source_locale, field);
+    }
+    
+    let memory = TranslationMemory::open(config)?;
+    if memory.enabled() {
+        match memory.lookup(source_locale, locale, &request.text) {
+            MemoryLookup::Exact(_) => {
+                println!("ℹ️  The translation memory has this exact text; translate would reuse it without calling the AI");
+            }
+            MemoryLookup::References(matches) => request.references = matches,
+            MemoryLookup::None => {}
+        }
+    }
+    
+    let prompt = build_prompt(&request);
+    println!("📄 Template: {} (chosen by {})", template.name, chosen_by);
+    println!("🌍 {} → {} · {}", source_locale, locale, field);
+    println!("{}", "─".repeat(50));
+    println!("{}", prompt);
+    println!("{}", "─".repeat(50));
+    let counter = TokenCounter::for_model(&ai_config.model)?;
+    println!("🔤 {} prompt tokens ({})", counter.count(&prompt), ai_config.model);
+    
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+struct EvalOptions {
+    /// Fixture file; `.rosetta/eval.json` or the built-in fixtures when not set
+    fixtures: Option<PathBuf>,
+    /// Locales to evaluate; empty means every locale the fixtures have references for
+    locales: Vec<String>,
+    model: Option<String>,
+    detailed: bool,
+}
+
+/// Run a template and the version it replaced over the fixtures and compare how they score
+async fn evaluate(config: &Config, store: &TemplateStore, name: &str, options: EvalOptions) -> Result<()> {
+    let template = store.get(name)?;
+    let (fixtures, fixtures_origin) = EvalFixtures::load(options.fixtures.as_deref())?;
+    let eval_locales: Vec<String> = if options.locales.is_empty() {
+        fixtures.locales().into_iter().collect()
+    } else {
+        for locale in &options.locales {
+            locales::validate_code(locale)?;
+        }
+        options.locales.clone()
+    };
+    let eval_glossary = fixtures.glossary(config.glossary.as_ref());
+    
+    let mut versions = Vec::new();
+    match store.previous_version(name)? {
+        Some(previous) => versions.push((previous.label, previous.body)),
+        None => println!("ℹ️  '{}' has no previous version; showing its scores only", name),
+    }
+    versions.push(("current".to_string(), template.body.clone()));
+    
+    // Every case with a reference in an evaluated locale, translated the way translate would
+    let mut cases: Vec<(TranslationRequest, String)> = Vec::new();
+    for case in &fixtures.cases {
+        for locale in &eval_locales {
+            let Some(reference) = case.references.get(locale) else {
+                continue;
+            };
+            let source_fields = LocaleFields::from([(case.field.clone(), case.source.clone())]);
+            let mut request = translate::request_for(config, &source_fields, locale, &case.field, &template);
+            request.source_locale = fixtures.source_locale.clone();
+            request.source_language = locales::prompt_name(&fixtures.source_locale);
+            request.context = fixtures.context.clone().or(request.context);
+            request.do_not_translate = glossary::protected_in(&eval_glossary, &case.source);
+            request.terms = glossary::renderings_in(&eval_glossary, locale, &case.source);
+            cases.push((request, reference.clone()));
+        }
+    }
+    if cases.is_empty() {
+        anyhow::bail!("No reference translations for {} in {}", eval_locales.join(", "), fixtures_origin);
+    }
+    
+    let default_ai = AiConfig::default();
+    let ai_config = config.ai.as_ref().unwrap_or(&default_ai);
+    let provider = provider::from_config(ai_config, options.model.as_deref())
+        .context("Failed to set up the AI provider")?;
+    let model = provider.model().to_string();
+    let prices = PriceTable::from_config(ai_config);
+    let counter = TokenCounter::for_model(&model)?;
+    
+    let estimate = versions.iter().fold(TokenUsage::default(), |mut total, (_, body)| {
+        for (request, _) in &cases {
+            let request = TranslationRequest { template: Some(body.clone()), ..request.clone() };
+            total += counter.estimate(&request, None);
+        }
+        total
+    });
+    println!("🧪 Evaluating '{}' with {} on {} case(s) from {} ({})", name, model, cases.len(), fixtures_origin, eval_locales.join(", "));
+    println!("💵 Estimated cost: ${:.4} for {} request(s)", prices.cost(&model, estimate).unwrap_or(0.0), cases.len() * versions.len());
+    
+    // Evaluations count against the same budgets as translate runs
+    let ledger = Ledger::open()?;
+    let limit = translate::spending_limit(ai_config, &ledger)?;
+    if let Some(limit) = &limit {
+        limit.check_estimate(&prices, &model, estimate, "evaluate fewer locales with --locales")?;
+    }
+    let mut session = AiSession {
+        provider: provider.as_ref(),
+        prices,
+        counter,
+        limit,
+        spend: RunSpend::default(),
+        over_budget: false,
+    };
+    
+    let pb = ProgressBar::new((cases.len() * versions.len()) as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos:>3}/{len:3} {msg}")
+            .context("Failed to create progress bar style")?
+            .progress_chars("##-"),
+    );
+    
+    let result = async {
+        let mut results: Vec<(String, Vec<EvalOutput>, EvalScores)> = Vec::new();
+        for (label, body) in &versions {
+            let mut outputs = Vec::new();
+            let mut usage = TokenUsage::default();
+            for (request, reference) in &cases {
+                pb.set_message(format!("{}: {} → {}", label, request.field, request.target_locale));
+                let request = TranslationRequest { template: Some(body.clone()), ..request.clone() };
+                let Some(translation) = session.translate(&request).await
+                    .with_context(|| format!("Failed to translate {} to {}", request.field, request.target_locale))?
+                else {
+                    let reason = session.limit.as_ref().map(|limit| limit.reason.as_str()).unwrap_or_default();
+                    anyhow::bail!("Stopped before going over {}; evaluate fewer locales with --locales", reason);
+                };
+                usage += translation.usage;
+                outputs.push(EvalOutput {
+                    locale: request.target_locale,
+                    field: request.field,
+                    source: request.text,
+                    reference: reference.clone(),
+                    output: translation.text,
+                });
+                pb.inc(1);
+            }
+            let scores = EvalScores::new(&outputs, &eval_glossary, usage, session.cost(usage));
+            results.push((label.clone(), outputs, scores));
+        }
+        Ok::<_, anyhow::Error>(results)
+    }
+    .await;
+    pb.finish_and_clear();
+    
+    // Record what was spent even when a request failed halfway through
+    session.record(&ledger, &config.app.bundle_id, &ai_config.provider);
+    let results = result?;
+    
+    if options.detailed {
+        for (label, outputs, _) in &results {
+            print!("{}", DataFormatter::format_eval_outputs(label, outputs, &eval_glossary));
+        }
+    }
+    let scores: Vec<(String, EvalScores)> = results.into_iter().map(|(label, _, scores)| (label, scores)).collect();
+    print!("{}", DataFormatter::format_template_eval(name, &scores));
+    
+    Ok(())
+}
+
+/// Run `$VISUAL` or `$EDITOR` (falling back to vi) on a file and wait for it to close
+fn open_editor(path: &Path) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    
+    // Editors are often configured with arguments, e.g. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("No editor configured")?;
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{}'; set $EDITOR", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(())
+}
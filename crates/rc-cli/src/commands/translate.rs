@@ -1,82 +1,595 @@
-use crate::config::Config;
+use crate::config::{AiConfig, Config};
 use crate::formatter::DataFormatter;
+use crate::glossary;
+use crate::ledger::{Ledger, LedgerEntry, RunSpend};
+use crate::locales;
+use crate::memory::{MemoryLookup, MemoryMatch, TranslationMemory};
+use crate::provenance::{self, DraftOutcome};
+use crate::provider::pricing::PriceTable;
+use crate::provider::tokens::TokenCounter;
+use crate::provider::{self, Shortening, TokenUsage, Translation, TranslationProvider, TranslationRequest};
+use crate::style;
+use crate::templates::{Template, TemplateResolver, TemplateStore};
+use crate::text_metrics::{char_count, field_limit, LengthFix};
+use crate::workspace::{read_source, LocaleFields, Workspace};
 use anyhow::{Context, Result};
-use serde_json::{json, Value};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::json;
+use std::collections::BTreeMap;
 use std::path::Path;
 
-pub async fn run(locales: Vec<String>, model: Option<String>, config_path: &Path) -> Result<()> {
+/// Recorded as the model of drafts reused from the translation memory
+const MEMORY_MODEL: &str = "translation-memory";
+
+/// Shortening requests per field before an over-long translation is left for manual review
+const MAX_SHORTEN_ATTEMPTS: usize = 2;
+
+#[derive(Debug, Clone, Default)]
+pub struct TranslateOptions {
+    /// Target locales; empty means every configured target locale
+    pub locales: Vec<String>,
+    pub model: Option<String>,
+    /// Replace fields that were edited or reviewed locally
+    pub overwrite: bool,
+    /// Translate the source text on App Store Connect instead of the local workspace
+    pub from_remote: bool,
+    /// Translate every source field, not only new or changed ones
+    pub all: bool,
+    /// Prompt template for every field, instead of the ones chosen in `[ai.templates]`
+    pub template: Option<String>,
+}
+
+pub async fn run(config_path: &Path, options: TranslateOptions) -> Result<()> {
     println!("Generating translations using AI...");
     
     let config = Config::load(config_path)
         .context("Failed to load configuration")?;
     
-    let target_locales = if locales.is_empty() {
+    let target_locales = if options.locales.is_empty() {
         config.app.target_locales.clone()
     } else {
-        locales
+        for locale in &options.locales {
+            crate::locales::validate_code(locale)?;
+        }
+        options.locales
     };
     
-    let ai_model = model.unwrap_or_else(|| {
-        config.ai.as_ref()
-            .map(|ai| ai.model.clone())
-            .unwrap_or_else(|| "gpt-4o-mini".to_string())
-    });
+    let default_ai = AiConfig::default();
+    let ai_config = config.ai.as_ref().unwrap_or(&default_ai);
+    let ai_model = options.model.clone().unwrap_or_else(|| ai_config.model.clone());
+    
+    let templates = TemplateResolver::new(&TemplateStore::open()?, ai_config, options.template.as_deref())?;
     
-    println!("🤖 Using AI model: {}", ai_model);
+    println!("🤖 Using AI model: {} ({})", ai_model, ai_config.provider);
     println!("🌍 Target locales: {}", target_locales.join(", "));
     
-    // Initialize Node.js runtime
-    rc_node::init_node_runtime()
-        .context("Failed to initialize Node.js runtime")?;
+    // Source text, including edits that have not been pushed yet
+    let source_locale = &config.app.default_locale;
+    let source_fields = read_source(&config, options.from_remote).await?;
     
-    // First, get current app data to translate
-    println!("📥 Fetching current app metadata...");
-    let app_data = rc_node::asc_download(config.app.bundle_id.clone()).await
-        .context("Failed to download app data")?;
+    // Only fields that are new or whose source changed since they were translated
+    let workspace = Workspace::open(&config)?;
+    let plan = TranslationPlan::new(&config, &workspace, &source_fields, &target_locales, options.all, options.overwrite)?;
     
-    // Extract source metadata (usually English)
-    let source_locale = &config.app.default_locale;
-    let source_metadata = app_data
-        .get("metadata")
-        .and_then(|m| m.get(source_locale))
-        .context("No source metadata found for translation")?
-        .clone();
-    
-    // Prepare translation request
-    let translation_request = json!({
-        "metadata": source_metadata,
-        "sourceLocale": source_locale,
-        "targetLocales": target_locales,
-        "context": format!("App Store metadata for {}", config.app.bundle_id)
+    print!("{}", DataFormatter::format_translation_plan(&plan.pending, &plan.kept, &plan.from_memory, &plan.references));
+    
+    if plan.is_empty() {
+        println!("✅ All translations are up to date (use --all to retranslate everything)");
+        return Ok(());
+    }
+    
+    let build_request = |locale: &str, field: &str| plan.request(&config, &source_fields, &templates, locale, field);
+    let requests = plan.requests(&config, &source_fields, &templates);
+    
+    let mut translations = BTreeMap::new();
+    let mut failure = None;
+    if !requests.is_empty() {
+        let mut template_use: BTreeMap<&str, usize> = BTreeMap::new();
+        for (locale, fields) in &plan.pending {
+            for field in fields {
+                *template_use.entry(templates.resolve(locale, field).name.as_str()).or_default() += 1;
+            }
+        }
+        let template_use: Vec<String> = template_use.iter().map(|(name, count)| format!("{} ({})", name, count)).collect();
+        println!("📄 Prompt templates: {}", template_use.join(", "));
+    
+        let provider = provider::from_config(ai_config, options.model.as_deref())
+            .context("Failed to set up the AI provider")?;
+        let prices = PriceTable::from_config(ai_config);
+        if prices.price(&ai_model).is_none() {
+            println!("ℹ️  No price known for {}; add [ai.pricing.\"{}\"] to the config to see costs", ai_model, ai_model);
+        }
+    
+        // Estimate with the model's tokenizer; fields translated before are expected to come back as long again
+        let counter = TokenCounter::for_model(&ai_model)?;
+        let estimate = requests.iter().fold(TokenUsage::default(), |mut total, request| {
+            total += counter.estimate(request, plan.existing(&request.target_locale, &request.field));
+            total
+        });
+    
+        // Refuse runs that are expected to go over budget before spending anything
+        let ledger = Ledger::open()?;
+        let limit = spending_limit(ai_config, &ledger)?;
+        if let Some(limit) = &limit {
+            limit.check_estimate(&prices, &ai_model, estimate, "translate fewer locales with --locales")?;
+        }
+    
+        let mut session = AiSession {
+            provider: provider.as_ref(),
+            prices,
+            counter,
+            limit,
+            spend: RunSpend::default(),
+            over_budget: false,
+        };
+        let result = async {
+            translate_with_ai(&mut session, estimate, requests, source_locale, &mut translations).await?;
+            // Fields over their App Store Connect limit are re-requested with the exact budget
+            shorten_overlong(&mut session, &mut translations, &build_request).await
+        }
+        .await;
+    
+        // Record what was spent even when a request failed halfway through the run
+        session.record(&ledger, &config.app.bundle_id, &ai_config.provider);
+        if let Some(limit) = session.limit.as_ref().filter(|_| session.over_budget) {
+            println!("⚠️  Stopped before going over {}; run translate again to finish the remaining fields", limit.reason);
+        }
+    
+        // Translations finished before a failed request are still saved below
+        match result {
+            Ok(length_fixes) if !length_fixes.is_empty() => {
+                print!("{}", DataFormatter::format_length_fixes(&length_fixes));
+            }
+            Ok(_) => {}
+            Err(e) => failure = Some(e),
+        }
+    }
+    
+    // Everything about to be saved, including memory hits approved before the glossary changed
+    let mut new_text = translations.clone();
+    for (locale, remembered) in &plan.from_memory {
+        new_text.entry(locale.clone()).or_default().extend(remembered.clone());
+    }
+    
+    // Protected terms and fixed renderings from the project glossary
+    if let Some(project_glossary) = config.glossary.as_ref().filter(|g| !glossary::is_empty(g)) {
+        let violations: BTreeMap<String, _> = new_text
+            .iter()
+            .map(|(locale, translated)| {
+                let found = glossary::check(project_glossary, locale, &source_fields, translated);
+                (locale.clone(), found)
+            })
+            .collect();
+        if !violations.is_empty() {
+            print!("{}", DataFormatter::format_glossary_violations(&violations));
+        }
+    }
+    
+    let style_issues: BTreeMap<String, _> = new_text
+        .iter()
+        .filter_map(|(locale, translated)| {
+            let guide = config.style.as_ref()?.get(locale)?;
+            Some((locale.clone(), style::check(locale, guide, translated)))
+        })
+        .collect();
+    if !style_issues.is_empty() {
+        print!("{}", DataFormatter::format_style_issues(&style_issues));
+    }
+    
+    // Save as drafts in the working copy so they can be reviewed, diffed and pushed
+    let mut saved = BTreeMap::new();
+    for (locale, (mut fields, mut locale_provenance)) in plan.targets {
+        let mut outcomes = BTreeMap::new();
+        if let Some(translated) = translations.get(&locale) {
+            outcomes.extend(provenance::apply_drafts(
+                &mut fields,
+                &mut locale_provenance,
+                translated,
+                &source_fields,
+                source_locale,
+                &ai_model,
+                options.overwrite,
+            ));
+        }
+        if let Some(remembered) = plan.from_memory.get(&locale) {
+            outcomes.extend(provenance::apply_drafts(
+                &mut fields,
+                &mut locale_provenance,
+                remembered,
+                &source_fields,
+                source_locale,
+                MEMORY_MODEL,
+                options.overwrite,
+            ));
+        }
+        if outcomes.is_empty() {
+            continue;
+        }
+    
+        if outcomes.values().any(|outcome| *outcome == DraftOutcome::Saved) {
+            workspace.write_locale(&locale, &fields)?;
+            workspace.write_provenance(&locale, &locale_provenance)?;
+        }
+        saved.insert(locale, outcomes);
+    }
+    
+    print!("{}", DataFormatter::format_saved_drafts(&saved, &workspace.current_dir()));
+    if let Some(e) = failure {
+        println!("⚠️  Translation stopped early; the drafts finished so far were saved. Run translate again to finish the rest.");
+        return Err(e);
+    }
+    println!("📝 Review translated content with 'rosetta-connect diff' before uploading with 'rosetta-connect push'");
+    
+    Ok(())
+}
+
+/// Which source fields a translate run sends to the AI for each target locale, and which it
+/// reuses from the translation memory or skips
+pub struct TranslationPlan {
+    /// Current fields and provenance of every target locale
+    pub targets: BTreeMap<String, (LocaleFields, provenance::LocaleProvenance)>,
+    /// Fields to send to the AI
+    pub pending: BTreeMap<String, Vec<String>>,
+    /// Fields whose source changed but whose translation was edited or reviewed locally
+    pub kept: BTreeMap<String, Vec<String>>,
+    /// Fields translated before, taken from the translation memory
+    pub from_memory: BTreeMap<String, LocaleFields>,
+    /// Similar translations from the memory, handed to the AI as references
+    pub references: BTreeMap<String, BTreeMap<String, Vec<MemoryMatch>>>,
+}
+
+impl TranslationPlan {
+    /// Plan translating the fields that are new or whose source changed since they were
+    /// translated (every source field with `all`) into `target_locales`
+    pub fn new(
+        config: &Config,
+        workspace: &Workspace,
+        source_fields: &LocaleFields,
+        target_locales: &[String],
+        all: bool,
+        overwrite: bool,
+    ) -> Result<Self> {
+        let source_locale = &config.app.default_locale;
+        let local_locales = workspace.locales()?;
+        let mut plan = Self {
+            targets: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            kept: BTreeMap::new(),
+            from_memory: BTreeMap::new(),
+            references: BTreeMap::new(),
+        };
+        
+        for locale in target_locales {
+            let fields = if local_locales.contains(locale) {
+                workspace.read_locale(locale)?
+            } else {
+                LocaleFields::new()
+            };
+            let locale_provenance = workspace.read_provenance(locale)?;
+            
+            let outdated = if all {
+                source_fields
+                    .iter()
+                    .filter(|(_, text)| !text.trim().is_empty())
+                    .map(|(field, _)| field.clone())
+                    .collect()
+            } else {
+                provenance::outdated_fields(source_fields, &fields, &locale_provenance)
+            };
+            
+            // Don't pay for translations that would not replace a local edit anyway
+            let (edited, outdated): (Vec<String>, Vec<String>) = outdated.into_iter().partition(|field| {
+                !overwrite
+                    && provenance::is_human_edited(fields.get(field).map(|v| v.as_str()), locale_provenance.get(field))
+            });
+            
+            plan.pending.insert(locale.clone(), outdated);
+            plan.kept.insert(locale.clone(), edited);
+            plan.targets.insert(locale.clone(), (fields, locale_provenance));
+        }
+        
+        // Reuse approved translations; similar ones are handed to the AI as references
+        let memory = TranslationMemory::open(config)?;
+        if memory.enabled() {
+            for (locale, fields) in plan.pending.iter_mut() {
+                fields.retain(|field| {
+                    let text = source_fields.get(field).map(|v| v.as_str()).unwrap_or("");
+                    match memory.lookup(source_locale, locale, text) {
+                        MemoryLookup::Exact(translation) => {
+                            plan.from_memory.entry(locale.clone()).or_default().insert(field.clone(), translation);
+                            false
+                        }
+                        MemoryLookup::References(matches) => {
+                            plan.references.entry(locale.clone()).or_default().insert(field.clone(), matches);
+                            true
+                        }
+                        MemoryLookup::None => true,
+                    }
+                });
+            }
+        }
+        
+        Ok(plan)
+    }
+    
+    /// Nothing to send to the AI and nothing to take from the memory
+    pub fn is_empty(&self) -> bool {
+        self.pending.values().all(|fields| fields.is_empty()) && self.from_memory.is_empty()
+    }
+    
+    /// Request for one field, with its translation memory references
+    pub fn request(
+        &self,
+        config: &Config,
+        source_fields: &LocaleFields,
+        templates: &TemplateResolver,
+        locale: &str,
+        field: &str,
+    ) -> TranslationRequest {
+        TranslationRequest {
+            references: self.references.get(locale).and_then(|fields| fields.get(field)).cloned().unwrap_or_default(),
+            ..request_for(config, source_fields, locale, field, templates.resolve(locale, field))
+        }
+    }
+    
+    /// Requests for every field sent to the AI
+    pub fn requests(&self, config: &Config, source_fields: &LocaleFields, templates: &TemplateResolver) -> Vec<TranslationRequest> {
+        self.pending
+            .iter()
+            .flat_map(|(locale, fields)| fields.iter().map(move |field| self.request(config, source_fields, templates, locale, field)))
+            .collect()
+    }
+    
+    /// Current translation of a field; the new one is expected to come back as long again
+    pub fn existing(&self, locale: &str, field: &str) -> Option<&str> {
+        self.targets.get(locale).and_then(|(fields, _)| fields.get(field)).map(|v| v.as_str())
+    }
+}
+
+/// Request to translate one source field into `locale` with `template`, the project's glossary
+/// and the locale's style guide; translation memory references are added by `run`
+pub fn request_for(config: &Config, source_fields: &LocaleFields, locale: &str, field: &str, template: &Template) -> TranslationRequest {
+    let text = source_fields.get(field).cloned().unwrap_or_default();
+    let project_glossary = config.glossary.as_ref().filter(|g| !glossary::is_empty(g));
+    let style_guide = config.style.as_ref().and_then(|guides| guides.get(locale));
+    
+    TranslationRequest {
+        field: field.to_string(),
+        source_locale: config.app.default_locale.clone(),
+        target_locale: locale.to_string(),
+        // Language names for the prompt come from the locale registry
+        source_language: locales::prompt_name(&config.app.default_locale),
+        target_language: locales::prompt_name(locale),
+        context: Some(format!("App Store metadata for {}", config.app.bundle_id)),
+        references: Vec::new(),
+        do_not_translate: project_glossary.map(|g| glossary::protected_in(g, &text)).unwrap_or_default(),
+        terms: project_glossary.map(|g| glossary::renderings_in(g, locale, &text)).unwrap_or_default(),
+        style: style_guide.map(|guide| style::prompt_instructions(locale, guide)).unwrap_or_default(),
+        shorten: None,
+        template: Some(template.body.clone()),
+        text,
+    }
+}
+
+/// Most a run may spend, and where that limit comes from
+pub(crate) struct SpendingLimit {
+    pub(crate) amount: f64,
+    pub(crate) reason: String,
+}
+
+impl SpendingLimit {
+    /// Refuse a run expected to go over the limit before spending anything; `hint` says how to
+    /// make the run smaller
+    pub(crate) fn check_estimate(&self, prices: &PriceTable, model: &str, estimate: TokenUsage, hint: &str) -> Result<()> {
+        let estimated = prices.cost(model, estimate).with_context(|| {
+            format!("No price known for {}; add [ai.pricing.\"{}\"] to the config so {} can be enforced", model, model, self.reason)
+        })?;
+        if estimated > self.amount {
+            anyhow::bail!(
+                "Estimated cost ${:.4} is over {}; {} or raise the limit in [ai]",
+                estimated,
+                self.reason,
+                hint
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The lower of `max_cost_per_run` and what is left of `monthly_budget` this month
+pub(crate) fn spending_limit(ai: &AiConfig, ledger: &Ledger) -> Result<Option<SpendingLimit>> {
+    let per_run = ai.max_cost_per_run.map(|amount| SpendingLimit {
+        amount,
+        reason: format!("max_cost_per_run (${})", amount),
     });
+    let monthly = match ai.monthly_budget {
+        Some(budget) => {
+            let left = budget - ledger.spent_this_month()?;
+            Some(SpendingLimit {
+                amount: left,
+                reason: format!("what is left of the monthly budget (${:.4} of ${})", left.max(0.0), budget),
+            })
+        }
+        None => None,
+    };
+    
+    Ok(match (per_run, monthly) {
+        (Some(a), Some(b)) => Some(if a.amount <= b.amount { a } else { b }),
+        (a, b) => a.or(b),
+    })
+}
+
+/// The provider of one `translate` or `template eval` run, and what the run has spent so far
+pub(crate) struct AiSession<'a> {
+    pub(crate) provider: &'a dyn TranslationProvider,
+    pub(crate) prices: PriceTable,
+    pub(crate) counter: TokenCounter,
+    pub(crate) limit: Option<SpendingLimit>,
+    pub(crate) spend: RunSpend,
+    /// Set once a request was held back because it would have gone over `limit`
+    pub(crate) over_budget: bool,
+}
+
+impl AiSession<'_> {
+    pub(crate) fn cost(&self, usage: TokenUsage) -> f64 {
+        self.prices.cost(self.provider.model(), usage).unwrap_or(0.0)
+    }
+    
+    /// Translate and record the tokens billed; `None` once the next request could take the run
+    /// over its spending limit
+    pub(crate) async fn translate(&mut self, request: &TranslationRequest) -> Result<Option<Translation>> {
+        if let Some(limit) = &self.limit {
+            let next = self.cost(self.counter.estimate(request, None));
+            if self.over_budget || self.spend.cost() + next > limit.amount {
+                self.over_budget = true;
+                return Ok(None);
+            }
+        }
     
-    // Estimate cost first
-    println!("💰 Estimating translation cost...");
-    let cost_estimate = rc_node::ai_estimate_cost(translation_request.clone()).await
-        .context("Failed to estimate translation cost")?;
+        let translation = self.provider.translate(request).await?;
+        let cost = self.cost(translation.usage);
+        self.spend.add(&request.target_locale, &request.field, translation.usage, cost);
+        Ok(Some(translation))
+    }
+    
+    /// Append what the run spent to the cost ledger; a ledger that can't be written is only reported
+    pub(crate) fn record(&self, ledger: &Ledger, app_id: &str, provider_name: &str) {
+        if self.spend.is_empty() {
+            return;
+        }
+        let model = self.provider.model();
+        let priced = self.prices.price(model).is_some();
+        let entry = LedgerEntry::new(app_id, provider_name, model, self.spend.clone(), priced);
+        if let Err(e) = ledger.append(&entry) {
+            println!("⚠️  Failed to record this run in the cost ledger: {:#}", e);
+        }
+    }
+}
+
+/// Ask again for every translated field over its character limit, stating the limit and the
+/// previous attempt, up to `MAX_SHORTEN_ATTEMPTS` times. The shortest translation received is
+/// kept; fields still over the limit are returned unresolved for manual review.
+async fn shorten_overlong(
+    session: &mut AiSession<'_>,
+    translations: &mut BTreeMap<String, LocaleFields>,
+    build_request: &dyn Fn(&str, &str) -> TranslationRequest,
+) -> Result<BTreeMap<String, Vec<LengthFix>>> {
+    let mut fixes: BTreeMap<String, Vec<LengthFix>> = BTreeMap::new();
+    for (locale, translated) in translations.iter() {
+        let over: Vec<LengthFix> = translated
+            .iter()
+            .filter_map(|(field, text)| {
+                let limit = field_limit(field)?;
+                let length = char_count(text);
+                (length > limit).then(|| LengthFix {
+                    field: field.clone(),
+                    limit,
+                    original: length,
+                    length,
+                    attempts: 0,
+                })
+            })
+            .collect();
+        if !over.is_empty() {
+            fixes.insert(locale.clone(), over);
+        }
+    }
+    if fixes.is_empty() {
+        return Ok(fixes);
+    }
+    
+    let spent_before = session.spend.cost();
+    for attempt in 1..=MAX_SHORTEN_ATTEMPTS {
+        let pending: usize = fixes.values().flatten().filter(|fix| !fix.resolved()).count();
+        if pending == 0 || session.over_budget {
+            break;
+        }
+        println!("✂️  Shortening {} translation(s) over the character limit (attempt {}/{})...", pending, attempt, MAX_SHORTEN_ATTEMPTS);
+    
+        for (locale, locale_fixes) in fixes.iter_mut() {
+            let Some(translated) = translations.get_mut(locale) else {
+                continue;
+            };
+            for fix in locale_fixes.iter_mut().filter(|fix| !fix.resolved()) {
+                let mut request = build_request(locale, &fix.field);
+                request.shorten = Some(Shortening {
+                    max_length: fix.limit,
+                    previous: translated.get(&fix.field).cloned().unwrap_or_default(),
+                });
+    
+                let Some(Translation { text, .. }) = session.translate(&request).await
+                    .with_context(|| format!("Failed to shorten {} for {}", fix.field, locale))?
+                else {
+                    break;
+                };
+                fix.attempts = attempt;
     
-    let cost_info = DataFormatter::format_cost_info(&cost_estimate);
+                let length = char_count(&text);
+                if length < fix.length {
+                    fix.length = length;
+                    translated.insert(fix.field.clone(), text);
+                }
+            }
+        }
+    }
+    println!("💵 Shortening cost: ${:.4}", session.spend.cost() - spent_before);
+    
+    Ok(fixes)
+}
+
+/// Show the estimate, then run every translation request, adding translated fields per locale to
+/// `translations` as they arrive so a failed request keeps the ones before it
+async fn translate_with_ai(
+    session: &mut AiSession<'_>,
+    estimate: TokenUsage,
+    requests: Vec<TranslationRequest>,
+    source_locale: &str,
+    translations: &mut BTreeMap<String, LocaleFields>,
+) -> Result<()> {
+    let cost_info = DataFormatter::format_cost_info(&json!({
+        "estimatedCost": session.cost(estimate),
+        "tokenEstimate": estimate.input + estimate.output,
+    }));
     print!("{}", cost_info);
     
     // Perform the actual translation
     println!("🔄 Starting AI translation...");
-    let translation_result = rc_node::ai_translate(translation_request).await
-        .context("Failed to translate metadata")?;
+    let pb = ProgressBar::new(requests.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos:>3}/{len:3} {msg}")
+            .context("Failed to create progress bar style")?
+            .progress_chars("##-"),
+    );
     
-    // Process and display results
-    if let Some(translations) = translation_result.get("translations") {
-        let formatted_results = DataFormatter::format_translation_results(translations, source_locale);
-        println!("{}", formatted_results);
+    for request in requests {
+        pb.set_message(format!("{} → {}", request.field, request.target_locale));
+        let text = match session.translate(&request).await {
+            Ok(Some(translation)) => translation.text,
+            Ok(None) => break,
+            Err(e) => {
+                pb.finish_and_clear();
+                return Err(e.context(format!("Failed to translate {} to {}", request.field, request.target_locale)));
+            }
+        };
+    
+        translations.entry(request.target_locale).or_default().insert(request.field, text);
+        pb.inc(1);
     }
+    pb.finish_and_clear();
+    
+    // Process and display results
+    let formatted_results = DataFormatter::format_translation_results(&json!(translations), source_locale);
+    println!("{}", formatted_results);
     
     // Display final cost information
-    let final_cost_info = DataFormatter::format_cost_info(&translation_result);
+    let usage = session.spend.usage();
+    let final_cost_info = DataFormatter::format_cost_info(&json!({
+        "totalCost": session.spend.cost(),
+        "tokensUsed": { "input": usage.input, "output": usage.output },
+    }));
     print!("{}", final_cost_info);
     
-    println!("✅ Translation completed for {} locales", target_locales.len());
-    
-    // TODO: Save translated metadata to local files for review before upload
-    println!("📝 Review translated content before uploading with 'rosetta-connect push'");
-    
+    println!("✅ Translation completed for {} locales", translations.len());
     Ok(())
-}
\ No newline at end of file
+}
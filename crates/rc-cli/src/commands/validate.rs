@@ -1,29 +1,107 @@
 use crate::config::Config;
+use crate::formatter::DataFormatter;
+use crate::provenance::stale_translations;
+use crate::validation::{validate, Severity, ValidationInput};
+use crate::workspace::Workspace;
 use anyhow::{Context, Result};
+use serde_json::json;
+use std::collections::BTreeMap;
 use std::path::Path;
 
-pub async fn run(config_path: &Path) -> Result<()> {
-    println!("Validating content against App Store guidelines...");
-    
-    let _config = Config::load(config_path)
+#[derive(Debug, Clone)]
+pub struct ValidateOptions {
+    pub locales: Vec<String>,
+    pub output_format: String,
+}
+
+impl Default for ValidateOptions {
+    fn default() -> Self {
+        Self {
+            locales: Vec::new(),
+            output_format: "table".to_string(),
+        }
+    }
+}
+
+pub async fn run(config_path: &Path, options: ValidateOptions) -> Result<()> {
+    let json_output = options.output_format == "json";
+    if !json_output {
+        println!("Validating content against App Store guidelines...");
+    }
+
+    let config = Config::load(config_path)
         .context("Failed to load configuration")?;
-    
-    // TODO: Implement actual validation logic
-    println!("🔍 Checking content compliance...");
-    
-    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-    
-    println!("✅ Validation Results:");
-    println!("   📝 App descriptions: All within character limits");
-    println!("   🏷️  Keywords: Appropriate and compliant");
-    println!("   🖼️  Screenshots: Correct dimensions and count");
-    println!("   🌍 Localization: Complete for all target locales");
-    
-    println!("\n⚠️  Warnings:");
-    println!("   • Description for zh-Hans could be more culturally specific");
-    println!("   • Consider adding more keywords for fr-FR locale");
-    
-    println!("\n📊 Summary: Ready for App Store submission");
-    
+
+    let workspace = Workspace::open(&config)?;
+    let mut locales = workspace.read_all()
+        .context("Failed to read local workspace")?;
+    if locales.is_empty() {
+        anyhow::bail!(
+            "No local metadata found in {}. Run 'rosetta-connect pull' first.",
+            workspace.current_dir().display()
+        );
+    }
+
+    // Staleness is judged against the full source locale, before any --locales filtering
+    let source = locales.get(&config.app.default_locale).cloned().unwrap_or_default();
+    let stale = stale_translations(&workspace, &config.app.default_locale, &source)?;
+
+    let mut expected_locales = vec![config.app.default_locale.clone()];
+    expected_locales.extend(config.app.target_locales.iter().cloned());
+    expected_locales.dedup();
+
+    if !options.locales.is_empty() {
+        locales.retain(|locale, _| options.locales.contains(locale));
+        expected_locales.retain(|locale| options.locales.contains(locale));
+    }
+
+    // Screenshot manifests live in the version snapshot the workspace was pulled from
+    let version = workspace.pulled_version()?;
+    let mut screenshots = BTreeMap::new();
+    for locale in locales.keys() {
+        let count = match &version {
+            Some(version) => workspace.screenshot_count(version, locale)?,
+            None => None,
+        };
+        screenshots.insert(locale.clone(), count);
+    }
+
+    let diagnostics = validate(&ValidationInput {
+        default_locale: &config.app.default_locale,
+        expected_locales: &expected_locales,
+        locales: &locales,
+        screenshots: &screenshots,
+        stale: &stale,
+    });
+
+    let count = |severity| diagnostics.iter().filter(|d| d.severity == severity).count();
+    let errors = count(Severity::Error);
+    let warnings = count(Severity::Warning);
+
+    match options.output_format.as_str() {
+        "json" => {
+            let output = json!({
+                "valid": errors == 0,
+                "diagnostics": diagnostics,
+                "summary": {
+                    "locales": locales.len(),
+                    "errors": errors,
+                    "warnings": warnings,
+                    "info": count(Severity::Info),
+                }
+            });
+            println!("{}", serde_json::to_string_pretty(&output)
+                .context("Failed to serialize diagnostics as JSON")?);
+        }
+        "table" => {
+            print!("{}", DataFormatter::format_diagnostics(&diagnostics, locales.len()));
+        }
+        other => anyhow::bail!("Unsupported output format: {}", other),
+    }
+
+    if errors > 0 {
+        std::process::exit(crate::EXIT_FOUND);
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,13 +1,21 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
+use crate::locales;
+use crate::templates;
+use crate::workspace::METADATA_FIELDS;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     pub app: AppConfig,
     pub assets: Option<AssetsConfig>,
     pub ai: Option<AiConfig>,
+    pub history: Option<HistoryConfig>,
+    pub memory: Option<MemoryConfig>,
+    pub glossary: Option<GlossaryConfig>,
+    /// Style guides keyed by locale code
+    pub style: Option<BTreeMap<String, StyleConfig>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -25,10 +33,44 @@ pub struct AssetsConfig {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AiConfig {
+    /// "openai", "ollama" or "openai-compatible"
     pub provider: String,
     pub model: String,
     pub temperature: f32,
     pub max_tokens: u32,
+    /// Chat completions endpoint, e.g. "http://localhost:8000/v1"; required for "openai-compatible"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Environment variable holding the API key; "openai" defaults to OPENAI_API_KEY
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// USD one `translate` or `template eval` run may spend; it stops before a request would go over
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_per_run: Option<f64>,
+    /// USD all `translate` and `template eval` runs in the project may spend per calendar month (UTC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_budget: Option<f64>,
+    /// Prices per model, overriding and extending the built-in table
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pricing: BTreeMap<String, ModelPrice>,
+    /// Prompt templates by field ("default" for the rest), and tables of them by locale
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, TemplateMapping>,
+}
+
+/// An entry of `[ai.templates]`: the template of a field, or a locale's own field → template table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TemplateMapping {
+    Template(String),
+    Locale(BTreeMap<String, String>),
+}
+
+/// USD per million tokens
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
 }
 
 impl Default for AiConfig {
@@ -38,17 +80,195 @@ impl Default for AiConfig {
             model: "gpt-4o-mini".to_string(),
             temperature: 0.7,
             max_tokens: 1024,
+            base_url: None,
+            api_key_env: None,
+            max_cost_per_run: None,
+            monthly_budget: None,
+            pricing: BTreeMap::new(),
+            templates: BTreeMap::new(),
+        }
+    }
+}
+
+/// Retention of the snapshot history in `.rosetta/history/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Number of snapshots to keep; older ones are pruned whenever a snapshot is recorded
+    #[serde(default = "default_keep_last")]
+    pub keep_last: usize,
+    /// Also prune snapshots older than this many days (the newest snapshot is always kept)
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+}
+
+fn default_keep_last() -> usize {
+    50
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            keep_last: default_keep_last(),
+            max_age_days: None,
+        }
+    }
+}
+
+/// Translation memory in `.rosetta/memory.json`, consulted by `translate` before calling the AI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryConfig {
+    #[serde(default = "default_memory_enabled")]
+    pub enabled: bool,
+    /// Minimum similarity (0–1) for a stored translation to be passed to the AI as a reference;
+    /// only exact matches are reused without calling the AI
+    #[serde(default = "default_fuzzy_threshold")]
+    pub fuzzy_threshold: f64,
+}
+
+fn default_memory_enabled() -> bool {
+    true
+}
+
+fn default_fuzzy_threshold() -> f64 {
+    0.85
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_memory_enabled(),
+            fuzzy_threshold: default_fuzzy_threshold(),
         }
     }
 }
 
+/// Product terms `translate` must keep or render consistently
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GlossaryConfig {
+    /// Brand names, handles and other terms kept exactly as written in every locale
+    #[serde(default)]
+    pub do_not_translate: Vec<String>,
+    /// Fixed translations of source terms, keyed by term and then by locale
+    #[serde(default)]
+    pub terms: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// How a locale addresses the reader
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Formality {
+    /// "Sie", "vous", です/ます
+    Formal,
+    /// "du", "tu", だ/である
+    Informal,
+}
+
+/// Style guide of one locale, added to the translation prompt and checked after translating
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StyleConfig {
+    #[serde(default)]
+    pub formality: Option<Formality>,
+    /// Free-form tone description, e.g. "friendly and concise"
+    #[serde(default)]
+    pub tone: Option<String>,
+    /// Opening and closing quotation mark, e.g. "«»" or "„“"
+    #[serde(default)]
+    pub quotes: Option<String>,
+    /// French typography: a non-breaking space before : ; ! ? » and after «
+    #[serde(default)]
+    pub nbsp_before_punctuation: bool,
+    /// Further instructions passed to the AI as written; not checked
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
 impl Config {
     pub fn load(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
         
-        toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+        let config: Config = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        config.validate()
+            .with_context(|| format!("Invalid config file: {}", path.display()))?;
+
+        Ok(config)
+    }
+
+    /// Reject locale codes App Store Connect would not accept
+    pub fn validate(&self) -> Result<()> {
+        locales::validate_code(&self.app.default_locale)
+            .context("Invalid [app].default_locale")?;
+
+        for locale in &self.app.target_locales {
+            locales::validate_code(locale)
+                .context("Invalid locale in [app].target_locales")?;
+        }
+
+        if let Some(memory) = &self.memory {
+            if !(memory.fuzzy_threshold > 0.0 && memory.fuzzy_threshold <= 1.0) {
+                anyhow::bail!("[memory].fuzzy_threshold must be between 0 and 1, got {}", memory.fuzzy_threshold);
+            }
+        }
+
+        if let Some(glossary) = &self.glossary {
+            for (term, renderings) in &glossary.terms {
+                for locale in renderings.keys() {
+                    locales::validate_code(locale)
+                        .with_context(|| format!("Invalid locale in [glossary.terms] for \"{}\"", term))?;
+                }
+            }
+        }
+
+        for (model, price) in self.ai.iter().flat_map(|ai| &ai.pricing) {
+            if price.input < 0.0 || price.output < 0.0 {
+                anyhow::bail!("[ai.pricing.\"{}\"] prices must not be negative", model);
+            }
+        }
+        if let Some(ai) = &self.ai {
+            if ai.max_cost_per_run.is_some_and(|limit| limit <= 0.0) {
+                anyhow::bail!("[ai].max_cost_per_run must be greater than 0");
+            }
+            if ai.monthly_budget.is_some_and(|budget| budget <= 0.0) {
+                anyhow::bail!("[ai].monthly_budget must be greater than 0");
+            }
+
+            let check_entry = |table: &str, field: &str, template: &str| -> Result<()> {
+                if field != "default" && !METADATA_FIELDS.contains(&field) {
+                    anyhow::bail!(
+                        "[{}].{} is not a metadata field; use \"default\" or one of: {}",
+                        table,
+                        field,
+                        METADATA_FIELDS.join(", ")
+                    );
+                }
+                templates::validate_name(template)
+                    .with_context(|| format!("Invalid template in [{}].{}", table, field))
+            };
+            for (key, mapping) in &ai.templates {
+                match mapping {
+                    TemplateMapping::Template(template) => check_entry("ai.templates", key, template)?,
+                    TemplateMapping::Locale(fields) => {
+                        locales::validate_code(key)
+                            .context("Invalid locale in [ai.templates]")?;
+                        for (field, template) in fields {
+                            check_entry(&format!("ai.templates.{}", key), field, template)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (locale, style) in self.style.iter().flatten() {
+            locales::validate_code(locale)
+                .context("Invalid locale in [style]")?;
+            if style.quotes.as_ref().is_some_and(|quotes| quotes.chars().count() != 2) {
+                anyhow::bail!("[style.{}].quotes must be an opening and a closing quotation mark, e.g. \"«»\"", locale);
+            }
+        }
+
+        Ok(())
     }
     
     pub fn save(&self, path: &Path) -> Result<()> {
@@ -77,6 +297,60 @@ impl Config {
                 },
             }),
             ai: Some(AiConfig::default()),
+            history: Some(HistoryConfig::default()),
+            memory: Some(MemoryConfig::default()),
+            glossary: None,
+            style: None,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(ai_templates: &str) -> Result<Config> {
+        let content = format!(
+            "[app]\nbundle_id = \"com.example.app\"\ndefault_locale = \"en-US\"\ntarget_locales = [\"ja\"]\n\n\
+             [ai]\nprovider = \"openai\"\nmodel = \"gpt-4o-mini\"\ntemperature = 0.3\nmax_tokens = 1000\n\n{}",
+            ai_templates
+        );
+        let config: Config = toml::from_str(&content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn error(ai_templates: &str) -> String {
+        format!("{:#}", parse(ai_templates).unwrap_err())
+    }
+
+    #[test]
+    fn templates_by_field_and_by_locale_are_accepted() {
+        let config = parse(
+            "[ai.templates]\ndefault = \"concise\"\nkeywords = \"keywords\"\n\n[ai.templates.ja]\nwhatsNew = \"release-notes\"\n",
+        )
+        .unwrap();
+
+        let templates = &config.ai.unwrap().templates;
+        assert!(matches!(&templates["keywords"], TemplateMapping::Template(name) if name == "keywords"));
+        assert!(matches!(&templates["ja"], TemplateMapping::Locale(fields) if fields["whatsNew"] == "release-notes"));
+    }
+
+    #[test]
+    fn template_entries_must_name_a_metadata_field() {
+        assert!(error("[ai.templates]\ntitle = \"concise\"\n")
+            .starts_with("[ai.templates].title is not a metadata field; use \"default\" or one of: name, subtitle"));
+        assert!(error("[ai.templates.ja]\ntitle = \"concise\"\n").starts_with("[ai.templates.ja].title is not a metadata field"));
+    }
+
+    #[test]
+    fn template_names_and_locales_are_checked() {
+        assert_eq!(
+            error("[ai.templates]\nkeywords = \"../keywords\"\n"),
+            "Invalid template in [ai.templates].keywords: Invalid template name '../keywords': use lowercase letters, digits, '-' and '_'"
+        );
+        assert_eq!(
+            error("[ai.templates.ja-JP]\nkeywords = \"keywords\"\n"),
+            "Invalid locale in [ai.templates]: 'ja-JP' is not an App Store Connect locale, did you mean 'ja' (Japanese)?"
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use crate::config::GlossaryConfig;
+use crate::glossary;
+use crate::locales;
+use crate::provider::TokenUsage;
+use crate::text_metrics::{char_count, field_limit};
+use crate::workspace::{LocaleFields, METADATA_FIELDS};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fixture set that ships with the CLI, used when the project has none of its own
+const BUILTIN_FIXTURES: &str = include_str!("../fixtures/template-eval.json");
+
+/// Source strings with reference translations that templates are evaluated against
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalFixtures {
+    pub source_locale: String,
+    /// Passed to the template as {{app_context}}
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub glossary: FixtureGlossary,
+    pub cases: Vec<EvalCase>,
+}
+
+/// Glossary of the fixtures, added to the project's `[glossary]`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureGlossary {
+    #[serde(default)]
+    pub do_not_translate: Vec<String>,
+    #[serde(default)]
+    pub terms: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EvalCase {
+    pub field: String,
+    pub source: String,
+    /// Reference translation by locale
+    pub references: BTreeMap<String, String>,
+}
+
+impl EvalFixtures {
+    /// Fixtures from `path`, else from the project's `.rosetta/eval.json`, else the built-in set;
+    /// also returns where they came from
+    pub fn load(path: Option<&Path>) -> Result<(Self, String)> {
+        let project: PathBuf = [".rosetta", "eval.json"].iter().collect();
+        let (content, origin) = match path {
+            Some(path) => (
+                fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read fixtures: {}", path.display()))?,
+                path.display().to_string(),
+            ),
+            None if project.is_file() => (
+                fs::read_to_string(&project)
+                    .with_context(|| format!("Failed to read fixtures: {}", project.display()))?,
+                project.display().to_string(),
+            ),
+            None => (BUILTIN_FIXTURES.to_string(), "built-in fixtures".to_string()),
+        };
+
+        let fixtures: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse fixtures: {}", origin))?;
+        fixtures.validate()
+            .with_context(|| format!("Invalid fixtures: {}", origin))?;
+        Ok((fixtures, origin))
+    }
+
+    fn validate(&self) -> Result<()> {
+        locales::validate_code(&self.source_locale)?;
+        if self.cases.is_empty() {
+            anyhow::bail!("No cases");
+        }
+        for (index, case) in self.cases.iter().enumerate() {
+            if !METADATA_FIELDS.contains(&case.field.as_str()) {
+                anyhow::bail!("Unknown field '{}', expected one of: {}", case.field, METADATA_FIELDS.join(", "));
+            }
+            // A case without references would be skipped silently and skew the scores
+            if case.references.is_empty() {
+                anyhow::bail!("Case {} ({}) has no reference translations", index + 1, case.field);
+            }
+            for (locale, reference) in &case.references {
+                locales::validate_code(locale)?;
+                if reference.trim().is_empty() {
+                    anyhow::bail!("Case {} ({}) has an empty {} reference", index + 1, case.field, locale);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every locale some case has a reference translation for
+    pub fn locales(&self) -> BTreeSet<String> {
+        self.cases.iter().flat_map(|case| case.references.keys().cloned()).collect()
+    }
+
+    /// The project glossary with the fixtures' terms added
+    pub fn glossary(&self, project: Option<&GlossaryConfig>) -> GlossaryConfig {
+        let mut merged = project.cloned().unwrap_or_default();
+        for term in &self.glossary.do_not_translate {
+            if !merged.do_not_translate.contains(term) {
+                merged.do_not_translate.push(term.clone());
+            }
+        }
+        for (term, renderings) in &self.glossary.terms {
+            merged.terms.entry(term.clone()).or_default().extend(renderings.clone());
+        }
+        merged
+    }
+}
+
+/// What a template produced for one case in one locale
+#[derive(Debug, Clone)]
+pub struct EvalOutput {
+    pub locale: String,
+    pub field: String,
+    pub source: String,
+    pub reference: String,
+    pub output: String,
+}
+
+impl EvalOutput {
+    pub fn within_limit(&self) -> bool {
+        field_limit(&self.field).is_none_or(|limit| char_count(&self.output) <= limit)
+    }
+
+    /// `None` when the source has no glossary terms to keep
+    pub fn glossary_kept(&self, glossary: &GlossaryConfig) -> Option<bool> {
+        let applies = !glossary::protected_in(glossary, &self.source).is_empty()
+            || !glossary::renderings_in(glossary, &self.locale, &self.source).is_empty();
+        if !applies {
+            return None;
+        }
+        let source = LocaleFields::from([(self.field.clone(), self.source.clone())]);
+        let translated = LocaleFields::from([(self.field.clone(), self.output.clone())]);
+        Some(glossary::check(glossary, &self.locale, &source, &translated).is_empty())
+    }
+
+    pub fn similarity(&self) -> f64 {
+        similarity(&self.output, &self.reference)
+    }
+}
+
+/// How one version of a template did over the whole fixture set
+#[derive(Debug, Clone, Default)]
+pub struct EvalScores {
+    pub outputs: usize,
+    pub within_limit: usize,
+    /// Outputs whose source had glossary terms, and how many of them kept every term
+    pub glossary_cases: usize,
+    pub glossary_kept: usize,
+    pub similarity: f64,
+    pub usage: TokenUsage,
+    pub cost: f64,
+}
+
+impl EvalScores {
+    pub fn new(outputs: &[EvalOutput], glossary: &GlossaryConfig, usage: TokenUsage, cost: f64) -> Self {
+        let kept: Vec<bool> = outputs.iter().filter_map(|output| output.glossary_kept(glossary)).collect();
+        let similarity = if outputs.is_empty() {
+            0.0
+        } else {
+            outputs.iter().map(EvalOutput::similarity).sum::<f64>() / outputs.len() as f64
+        };
+
+        Self {
+            outputs: outputs.len(),
+            within_limit: outputs.iter().filter(|output| output.within_limit()).count(),
+            glossary_cases: kept.len(),
+            glossary_kept: kept.iter().filter(|kept| **kept).count(),
+            similarity,
+            usage,
+            cost,
+        }
+    }
+
+    pub fn limit_rate(&self) -> f64 {
+        ratio(self.within_limit, self.outputs)
+    }
+
+    pub fn glossary_rate(&self) -> Option<f64> {
+        (self.glossary_cases > 0).then(|| ratio(self.glossary_kept, self.glossary_cases))
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// Edit-distance similarity (0–1) ignoring case and differences in whitespace
+pub fn similarity(output: &str, reference: &str) -> f64 {
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    strsim::normalized_levenshtein(&normalize(output), &normalize(reference))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(locale: &str, field: &str, source: &str, output: &str) -> EvalOutput {
+        EvalOutput {
+            locale: locale.to_string(),
+            field: field.to_string(),
+            source: source.to_string(),
+            reference: String::new(),
+            output: output.to_string(),
+        }
+    }
+
+    fn glossary() -> GlossaryConfig {
+        GlossaryConfig {
+            do_not_translate: vec!["Habitly".to_string()],
+            terms: BTreeMap::from([("streak".to_string(), BTreeMap::from([("de-DE".to_string(), "Serie".to_string())]))]),
+        }
+    }
+
+    fn fixtures(cases: &str) -> Result<EvalFixtures> {
+        let fixtures: EvalFixtures = serde_json::from_str(&format!(r#"{{ "sourceLocale": "en-US", "cases": {} }}"#, cases))?;
+        fixtures.validate()?;
+        Ok(fixtures)
+    }
+
+    #[test]
+    fn similarity_ignores_case_and_whitespace() {
+        assert_eq!(similarity("Gewohnheiten, die bleiben", "Gewohnheiten, die bleiben"), 1.0);
+        assert_eq!(similarity("  gewohnheiten,\n die  BLEIBEN ", "Gewohnheiten, die bleiben"), 1.0);
+        assert_eq!(similarity("abc", "xyz"), 0.0);
+        assert!((similarity("Serie", "Series") - 5.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn similarity_of_empty_text() {
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("   ", "Serie"), 0.0);
+    }
+
+    #[test]
+    fn glossary_kept_only_applies_to_sources_with_terms() {
+        let glossary = glossary();
+        assert_eq!(output("de-DE", "name", "Habitly: Habit Tracker", "Habitly: Gewohnheiten").glossary_kept(&glossary), Some(true));
+        assert_eq!(output("de-DE", "name", "Habitly: Habit Tracker", "Habitli: Gewohnheiten").glossary_kept(&glossary), Some(false));
+        assert_eq!(output("de-DE", "subtitle", "Keep your streak", "Halte deine Strähne").glossary_kept(&glossary), Some(false));
+        assert_eq!(output("de-DE", "subtitle", "Build habits", "Gewohnheiten aufbauen").glossary_kept(&glossary), None);
+        // Terms without a rendering for the locale don't apply
+        assert_eq!(output("ja", "subtitle", "Keep your streak", "連続記録を続けよう").glossary_kept(&glossary), None);
+    }
+
+    #[test]
+    fn scores_average_similarity_and_count_limits_and_glossary() {
+        let mut outputs = vec![
+            output("de-DE", "name", "Habitly", "Habitly"),
+            output("de-DE", "subtitle", "Habitly keeps you going", "Habitli hält dich in Schwung, jeden Tag aufs Neue"),
+            output("de-DE", "keywords", "habit,tracker", "gewohnheit,tracker"),
+        ];
+        outputs[0].reference = "Habitly".to_string();
+        outputs[1].reference = "Habitli hält dich in Schwung, jeden Tag aufs Neue".to_string();
+        outputs[2].reference = "xyz".to_string();
+        let usage = TokenUsage { input: 300, output: 60 };
+
+        let scores = EvalScores::new(&outputs, &glossary(), usage, 0.002);
+        assert_eq!(scores.outputs, 3);
+        // The subtitle is over the 30 character limit
+        assert_eq!(scores.within_limit, 2);
+        assert_eq!((scores.glossary_cases, scores.glossary_kept), (2, 1));
+        assert_eq!(scores.glossary_rate(), Some(0.5));
+        let expected = (1.0 + 1.0 + similarity("gewohnheit,tracker", "xyz")) / 3.0;
+        assert!((scores.similarity - expected).abs() < 1e-9);
+        assert_eq!((scores.usage.input, scores.cost), (300, 0.002));
+    }
+
+    #[test]
+    fn scores_of_no_outputs_are_zero() {
+        let scores = EvalScores::new(&[], &glossary(), TokenUsage::default(), 0.0);
+        assert_eq!((scores.outputs, scores.similarity, scores.limit_rate()), (0, 0.0, 0.0));
+        assert_eq!(scores.glossary_rate(), None);
+    }
+
+    #[test]
+    fn builtin_fixtures_are_valid() {
+        let fixtures: EvalFixtures = serde_json::from_str(BUILTIN_FIXTURES).unwrap();
+        assert!(fixtures.validate().is_ok());
+        assert!(fixtures.locales().contains("ja"));
+    }
+
+    #[test]
+    fn malformed_fixtures_are_rejected() {
+        let error = |cases: &str| fixtures(cases).unwrap_err().to_string();
+
+        assert_eq!(error("[]"), "No cases");
+        assert_eq!(
+            error(r#"[{ "field": "name", "source": "Habitly", "references": {} }]"#),
+            "Case 1 (name) has no reference translations"
+        );
+        assert_eq!(
+            error(r#"[{ "field": "name", "source": "Habitly", "references": { "ja": "Habitly" } }, { "field": "subtitle", "source": "Build habits", "references": { "ja": " " } }]"#),
+            "Case 2 (subtitle) has an empty ja reference"
+        );
+        assert!(error(r#"[{ "field": "title", "source": "Habitly", "references": { "ja": "Habitly" } }]"#).starts_with("Unknown field 'title'"));
+        assert!(error(r#"[{ "field": "name", "source": "Habitly", "references": { "ja-JP": "Habitly" } }]"#).contains("did you mean 'ja'"));
+        // A case without the reference map at all doesn't parse
+        assert!(fixtures(r#"[{ "field": "name", "source": "Habitly" }]"#).is_err());
+    }
+}
@@ -1,89 +1,31 @@
 use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
-use comfy_table::{Table, presets::UTF8_FULL, ContentArrangement, Cell};
+use std::path::{Path, PathBuf};
+use comfy_table::{Table, presets::UTF8_FULL, ContentArrangement, Cell, Color};
 use owo_colors::OwoColorize;
 use std::collections::BTreeMap;
+use crate::product_page::{Clipped, PageRender};
+use crate::provenance::DraftOutcome;
+use crate::style::StyleIssue;
+use crate::text_metrics::{char_count, LengthFix, display_width, field_limit, pad_to_width, prefix, truncate, truncate_to_width, wrap};
+use crate::history::{Snapshot, SnapshotSource};
+use crate::ledger::{ReportGroup, SpendRow};
+use crate::glossary::{GlossaryViolation, ViolationKind};
+use crate::locales;
+use crate::memory::MemoryMatch;
+use crate::workspace::{LocaleFields, ScreenshotSet, METADATA_FIELDS};
+use crate::config::GlossaryConfig;
+use crate::evaluation::{EvalOutput, EvalScores};
+use crate::changes::{ChangeKind, FieldChange, ScreenshotChange, ScreenshotChangeKind};
+use crate::validation::{Diagnostic, Severity};
+use similar::{ChangeTag, TextDiff};
+
+/// Fields long enough to be shown as an inline word diff rather than old/new lines
+const LONG_TEXT_FIELDS: [&str; 3] = ["description", "whatsNew", "promotionalText"];
 
 pub struct DataFormatter;
 
 impl DataFormatter {
-    /// Format app metadata in a structured, readable way
-    pub fn format_app_metadata(data: &Value) -> String {
-        let mut output = String::new();
-        
-        if let Some(app_id) = data.get("appId").and_then(|v| v.as_str()) {
-            output.push_str(&format!("📱 App: {}\n", app_id));
-        }
-        
-        if let Some(locales) = data.get("locales").and_then(|v| v.as_array()) {
-            let locale_list: Vec<String> = locales
-                .iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| s.to_string())
-                .collect();
-            output.push_str(&format!("🌍 Available Locales: {}\n\n", locale_list.join(", ")));
-        }
-        
-        if let Some(metadata) = data.get("metadata").and_then(|v| v.as_object()) {
-            // Get default locale from the data, fallback to en-US
-            let default_locale = data.get("defaultLocale")
-                .and_then(|v| v.as_str())
-                .unwrap_or("en-US");
-                
-            // Sort locales, but prioritize default locale first
-            let mut sorted_locales: Vec<String> = metadata.keys().cloned().collect();
-            sorted_locales.sort();
-            
-            // Move default locale to front if it exists
-            if let Some(pos) = sorted_locales.iter().position(|x| x == default_locale) {
-                let default = sorted_locales.remove(pos);
-                sorted_locales.insert(0, default);
-            }
-            
-            for locale in sorted_locales {
-                if let Some(locale_data) = metadata.get(&locale) {
-                    output.push_str(&Self::format_locale_metadata(&locale, locale_data));
-                    output.push('\n');
-                }
-            }
-        }
-        
-        output
-    }
-    
-    /// Format metadata for a specific locale
-    pub fn format_locale_metadata(locale: &str, data: &Value) -> String {
-        let mut output = String::new();
-        let locale_flag = Self::get_locale_flag(locale);
-        
-        output.push_str(&format!("{} {}\n", locale_flag, locale));
-        output.push_str(&"─".repeat(40));
-        output.push('\n');
-        
-        if let Some(obj) = data.as_object() {
-            // Define the order we want to display fields
-            let field_order = ["name", "description", "keywords", "whatsNew"];
-            
-            for field in &field_order {
-                if let Some(value) = obj.get(*field).and_then(|v| v.as_str()) {
-                    output.push_str(&Self::format_metadata_field(field, value));
-                }
-            }
-            
-            // Display any other fields not in the standard order
-            for (key, value) in obj {
-                if !field_order.contains(&key.as_str()) {
-                    if let Some(text) = value.as_str() {
-                        output.push_str(&Self::format_metadata_field(key, text));
-                    }
-                }
-            }
-        }
-        
-        output
-    }
-
     /// Format only the default locale's metadata
     pub fn format_default_locale(data: &Value) -> String {
         let default_locale = data
@@ -98,10 +40,18 @@ impl DataFormatter {
             Cell::new("Value").add_attribute(comfy_table::Attribute::Bold),
         ]);
 
+        // Pre-wrap values by display width: comfy-table only breaks on spaces and byte-blind
+        // ANSI codes would throw off its column sizing, so styling is applied per cell instead
+        let value_width = Self::value_column_width(&table, "What's New");
+
         let mut add_row = |k: &str, v: Option<&str>| {
-            let key = k.blue().to_string();
+            let key = Cell::new(k).fg(Color::Blue);
             let val = v.unwrap_or("");
-            let styled_val = if val.is_empty() { "—".dimmed().to_string() } else { val.green().to_string() };
+            let styled_val = if val.is_empty() {
+                Cell::new("—").add_attribute(comfy_table::Attribute::Dim)
+            } else {
+                Cell::new(wrap(val, value_width).join("\n")).fg(Color::Green)
+            };
             table.add_row(vec![key, styled_val]);
         };
 
@@ -120,117 +70,21 @@ impl DataFormatter {
         format!("{}\n{}\n\n{}\n\n", "Default Locale".bold(), "—".repeat(18), table)
     }
 
-    /// Show a completeness table for the default locale and derive an overall status
-    /// Status: 上线 / 未上线 / 有错误
-    pub fn format_completeness_table(data: &Value) -> String {
-        let mut lines: Vec<String> = Vec::new();
-
-        let default_locale = data
-            .get("defaultLocale")
-            .and_then(|v| v.as_str())
-            .unwrap_or("en-US");
-
-        let app_id = data.get("appId").and_then(|v| v.as_str()).unwrap_or("");
-        let version = data.get("appVersion").and_then(|v| v.as_str()).unwrap_or("1.0.0");
-
-        let meta = data
-            .get("metadata")
-            .and_then(|m| m.get(default_locale))
-            .cloned()
-            .unwrap_or(Value::Null);
-
-        let obj = meta.as_object();
-        let name_ok = obj.and_then(|o| o.get("name")).and_then(|v| v.as_str()).map(|s| !s.trim().is_empty()).unwrap_or(false);
-        let subtitle_ok = obj.and_then(|o| o.get("subtitle")).and_then(|v| v.as_str()).map(|s| !s.trim().is_empty()).unwrap_or(false);
-        let desc_len = obj.and_then(|o| o.get("description")).and_then(|v| v.as_str()).map(|s| s.len()).unwrap_or(0);
-        let keywords_len = obj.and_then(|o| o.get("keywords")).and_then(|v| v.as_str()).map(|s| s.len()).unwrap_or(0);
-        let whats_new_ok = obj.and_then(|o| o.get("whatsNew")).and_then(|v| v.as_str()).map(|s| !s.trim().is_empty()).unwrap_or(false);
-
-        // Validate constraints
-        let mut errors: Vec<String> = Vec::new();
-        if desc_len > 4000 { errors.push(format!("Description too long ({} > 4000)", desc_len)); }
-        if keywords_len > 100 { errors.push(format!("Keywords exceed 100 chars ({} > 100)", keywords_len)); }
-
-        // Screenshots: read manifest `<PWD>/<appId>/<version>/<locale>/screenshots.json`
-        let mut screenshot_total: usize = 0;
-        let mut screenshot_err = None;
-        if !app_id.is_empty() {
-            let mut p = PathBuf::new();
-            if let Ok(cwd) = std::env::current_dir() {
-                p.push(cwd);
-            }
-            p.push(app_id);
-            p.push(version);
-            p.push(default_locale);
-            p.push("screenshots.json");
-            match fs::read_to_string(&p) {
-                Ok(s) => {
-                    if let Ok(json) = serde_json::from_str::<Value>(&s) {
-                        if let Some(sets) = json.get("sets").and_then(|v| v.as_array()) {
-                            for set in sets {
-                                if let Some(items) = set.get("items").and_then(|v| v.as_array()) {
-                                    screenshot_total += items.len();
-                                }
-                            }
-                        }
-                    } else {
-                        screenshot_err = Some("Invalid screenshots.json".to_string());
-                    }
-                }
-                Err(_) => {
-                    screenshot_err = Some("screenshots.json not found".to_string());
-                }
-            }
-        }
-
-        // Compose table
-        lines.push("🧪 完整性检查".to_string());
-        lines.push("=".repeat(30));
-        lines.push(String::new());
-
-        let mut add_row = |item: &str, ok: bool, note: String| {
-            let status = if ok { "✅ 完成" } else { "⚠️  缺失" };
-            lines.push(format!("- {:<12} | {:<6} | {}", item, status, note));
-        };
-
-        add_row("App Name", name_ok, String::new());
-        add_row("Subtitle", subtitle_ok, String::new());
-        add_row("Description", desc_len > 0, format!("{} chars", desc_len));
-        add_row("Keywords", keywords_len > 0, format!("{} / 100 chars", keywords_len));
-        add_row("What's New", whats_new_ok, String::new());
-
-        let screenshots_ok = screenshot_total > 0;
-        let screenshots_note = if let Some(e) = screenshot_err { e } else { format!("{} images", screenshot_total) };
-        add_row("Screenshots", screenshots_ok, screenshots_note);
-
-        lines.push(String::new());
-
-        // Determine overall status
-        let overall_status = if !errors.is_empty() {
-            "有错误"
-        } else if name_ok && desc_len > 0 && keywords_len > 0 && screenshots_ok {
-            "上线"
-        } else {
-            "未上线"
-        };
-
-        if !errors.is_empty() {
-            lines.push("错误:".to_string());
-            for e in errors { lines.push(format!("- {}", e)); }
-            lines.push(String::new());
-        }
-
-        lines.push(format!("上线状态: {}", overall_status));
-        lines.push(String::new());
-
-        lines.join("\n")
+    /// Columns left for the value of a two-column Field | Value table
+    fn value_column_width(table: &Table, widest_key: &str) -> usize {
+        // Borders and padding of a two-column UTF8_FULL table take 7 columns
+        let total = table.width().map(usize::from).unwrap_or(100);
+        total.saturating_sub(display_width(widest_key) + 7).max(20)
     }
 
-    /// Show a compact multi-locale status table
-    /// Columns: Locale | Text | Screenshots | Status
-    pub fn format_locales_status_table(data: &Value) -> String {
-        let mut output = String::new();
-
+    /// Show a compact multi-locale status table; `stale` lists translated fields whose source changed,
+    /// `untracked` fields without provenance whose staleness is unknown
+    /// Columns: Locale | Text | Screenshots | Translation | Status
+    pub fn format_locales_status_table(
+        data: &Value,
+        stale: &BTreeMap<String, Vec<String>>,
+        untracked: &BTreeMap<String, Vec<String>>,
+    ) -> String {
         let app_id = data.get("appId").and_then(|v| v.as_str()).unwrap_or("");
         let version = data.get("appVersion").and_then(|v| v.as_str()).unwrap_or("1.0.0");
         let default_locale = data.get("defaultLocale").and_then(|v| v.as_str()).unwrap_or("en-US");
@@ -256,6 +110,9 @@ impl DataFormatter {
             locales.insert(0, def);
         }
 
+        let desc_limit = field_limit("description").unwrap_or(4000);
+        let keywords_limit = field_limit("keywords").unwrap_or(100);
+
         // Table header
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
@@ -264,6 +121,7 @@ impl DataFormatter {
             Cell::new("Locale").add_attribute(comfy_table::Attribute::Bold),
             Cell::new("Text").add_attribute(comfy_table::Attribute::Bold),
             Cell::new("Screenshots").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Translation").add_attribute(comfy_table::Attribute::Bold),
             Cell::new("Status").add_attribute(comfy_table::Attribute::Bold),
         ]);
 
@@ -273,20 +131,20 @@ impl DataFormatter {
             let obj = meta.as_object();
             let name_ok = obj.and_then(|o| o.get("name")).and_then(|v| v.as_str()).map(|s| !s.trim().is_empty()).unwrap_or(false);
             let subtitle_ok = obj.and_then(|o| o.get("subtitle")).and_then(|v| v.as_str()).map(|s| !s.trim().is_empty()).unwrap_or(false);
-            let desc_len = obj.and_then(|o| o.get("description")).and_then(|v| v.as_str()).map(|s| s.len()).unwrap_or(0);
-            let keywords_len = obj.and_then(|o| o.get("keywords")).and_then(|v| v.as_str()).map(|s| s.len()).unwrap_or(0);
+            let desc_len = obj.and_then(|o| o.get("description")).and_then(|v| v.as_str()).map(char_count).unwrap_or(0);
+            let keywords_len = obj.and_then(|o| o.get("keywords")).and_then(|v| v.as_str()).map(char_count).unwrap_or(0);
             let whats_new_ok = obj.and_then(|o| o.get("whatsNew")).and_then(|v| v.as_str()).map(|s| !s.trim().is_empty()).unwrap_or(false);
 
             let text_ok_count = (name_ok as u8)
                 + (subtitle_ok as u8)
                 + ((desc_len > 0) as u8)
-                + ((keywords_len > 0 && keywords_len <= 100) as u8)
+                + ((keywords_len > 0 && keywords_len <= keywords_limit) as u8)
                 + (whats_new_ok as u8);
 
             // Errors
             let mut has_error = false;
-            if desc_len > 4000 { has_error = true; }
-            if keywords_len > 100 { has_error = true; }
+            if desc_len > desc_limit { has_error = true; }
+            if keywords_len > keywords_limit { has_error = true; }
 
             // Screenshots count from manifest
             let mut screenshots = 0usize;
@@ -313,15 +171,33 @@ impl DataFormatter {
             // Overall status
             let status = if has_error { "有错误" } else if text_ok_count >= 4 && screenshots > 0 { "上线" } else { "未上线" };
             let status_colored = match status {
-                "上线" => status.green().to_string(),
-                "未上线" => status.yellow().to_string(),
-                _ => status.red().to_string(),
+                "上线" => Cell::new(status).fg(Color::Green),
+                "未上线" => Cell::new(status).fg(Color::Yellow),
+                _ => Cell::new(status).fg(Color::Red),
+            };
+
+            // Style through comfy-table so ANSI codes don't count towards column widths
+            let locale_cell = if locale == default_locale {
+                Cell::new(&locale).add_attribute(comfy_table::Attribute::Bold)
+            } else {
+                Cell::new(&locale)
+            };
+
+            // Whether the translation still matches the current source text
+            let translation_cell = match stale.get(&locale) {
+                _ if locale == default_locale => Cell::new("源语言").add_attribute(comfy_table::Attribute::Dim),
+                Some(fields) if !fields.is_empty() => Cell::new(format!("过时: {}", fields.join(", "))).fg(Color::Yellow),
+                _ => match untracked.get(&locale) {
+                    Some(fields) if !fields.is_empty() => Cell::new(format!("未跟踪: {}", fields.join(", "))).fg(Color::Cyan),
+                    _ => Cell::new("最新").fg(Color::Green),
+                },
             };
 
             table.add_row(vec![
-                if locale == default_locale { locale.bold().to_string() } else { locale.to_string() },
-                format!("{}/5", text_ok_count),
-                format!("{}", screenshots),
+                locale_cell,
+                Cell::new(format!("{}/5", text_ok_count)),
+                Cell::new(screenshots),
+                translation_cell,
                 status_colored,
             ]);
         }
@@ -329,81 +205,19 @@ impl DataFormatter {
         format!("{}\n{}\n\n{}\n", "🌍 多语言状态".bold(), "—".repeat(18), table)
     }
     
-    /// Format a single metadata field with appropriate icon and wrapping
-    fn format_metadata_field(field: &str, value: &str) -> String {
-        let (icon, label) = match field {
+    /// Icon and display label of a metadata field
+    pub fn field_label(field: &str) -> (&'static str, &str) {
+        match field {
             "name" => ("📱", "App Name"),
             "description" => ("📝", "Description"),
             "keywords" => ("🔍", "Keywords"),
             "whatsNew" => ("📢", "What's New"),
             "subtitle" => ("📋", "Subtitle"),
+            "promotionalText" => ("📣", "Promotional Text"),
             _ => ("📄", field),
-        };
-        
-        let wrapped_value = Self::wrap_text(value, 60);
-        format!("   {} {}: {}\n", icon, label, wrapped_value)
-    }
-    
-    /// Wrap long text with proper indentation
-    fn wrap_text(text: &str, width: usize) -> String {
-        if text.len() <= width {
-            return text.to_string();
-        }
-        
-        let words: Vec<&str> = text.split_whitespace().collect();
-        let mut lines = Vec::new();
-        let mut current_line = String::new();
-        
-        for word in words {
-            if current_line.len() + word.len() + 1 <= width {
-                if !current_line.is_empty() {
-                    current_line.push(' ');
-                }
-                current_line.push_str(word);
-            } else {
-                if !current_line.is_empty() {
-                    lines.push(current_line);
-                    current_line = String::new();
-                }
-                current_line.push_str(word);
-            }
-        }
-        
-        if !current_line.is_empty() {
-            lines.push(current_line);
-        }
-        
-        // Join lines with proper indentation for continuation
-        if lines.len() == 1 {
-            lines[0].clone()
-        } else {
-            let mut result = lines[0].clone();
-            for line in &lines[1..] {
-                result.push_str(&format!("\n      {}", line));
-            }
-            result
         }
     }
-    
-    /// Get emoji flag for locale
-    fn get_locale_flag(locale: &str) -> &'static str {
-        match locale {
-            "en-US" => "🇺🇸",
-            "zh-Hans" => "🇨🇳", 
-            "zh-Hant" => "🇹🇼",
-            "fr-FR" => "🇫🇷",
-            "de-DE" => "🇩🇪",
-            "ja-JP" => "🇯🇵",
-            "ko-KR" => "🇰🇷",
-            "es-ES" => "🇪🇸",
-            "pt-BR" => "🇧🇷",
-            "it-IT" => "🇮🇹",
-            "ru-RU" => "🇷🇺",
-            "ar-SA" => "🇸🇦",
-            _ => "🌐",
-        }
-    }
-    
+
     /// Format translation results in a clean table-like format
     pub fn format_translation_results(translations: &Value, source_locale: &str) -> String {
         let mut output = String::new();
@@ -433,7 +247,7 @@ impl DataFormatter {
     /// Format a summary of translations for one locale
     fn format_translation_summary(locale: &str, data: &Value) -> String {
         let mut output = String::new();
-        let locale_flag = Self::get_locale_flag(locale);
+        let locale_flag = locales::flag(locale);
         
         output.push_str(&format!("\n{} {} Translation\n", locale_flag, locale));
         output.push_str(&"─".repeat(30));
@@ -445,27 +259,533 @@ impl DataFormatter {
             }
             
             if let Some(desc) = obj.get("description").and_then(|v| v.as_str()) {
-                let preview = if desc.len() > 80 {
-                    format!("{}...", &desc[..77])
-                } else {
-                    desc.to_string()
-                };
-                output.push_str(&format!("📝 {}\n", preview));
+                output.push_str(&format!("📝 {}\n", truncate(desc, 80)));
             }
             
             if let Some(keywords) = obj.get("keywords").and_then(|v| v.as_str()) {
-                let preview = if keywords.len() > 50 {
-                    format!("{}...", &keywords[..47])
+                output.push_str(&format!("🔍 {}\n", truncate(keywords, 50)));
+            }
+        }
+        
+        output
+    }
+    
+    /// Locales whose translated fields were made from an older version of the source text, and
+    /// locales with hand-written fields that have no provenance to check
+    pub fn format_stale_translations(
+        stale: &BTreeMap<String, Vec<String>>,
+        untracked: &BTreeMap<String, Vec<String>>,
+        source_locale: &str,
+    ) -> String {
+        let mut output = format!("\n🌐 {} Translations\n{}\n", "Local".bold(), "─".repeat(30));
+
+        for (locale, fields) in untracked {
+            let labels: Vec<&str> = fields.iter().map(|field| Self::field_label(field).1).collect();
+            output.push_str(&format!(
+                "   {} {} {:<8} {}\n",
+                "❔".cyan(),
+                locales::flag(locale),
+                locale,
+                format!("untracked: {}", labels.join(", ")).cyan()
+            ));
+        }
+        if !untracked.is_empty() {
+            output.push_str(&format!(
+                "   No record of which {} text these were written from. Run {} to start tracking them.\n",
+                source_locale,
+                "rosetta-connect review".bright_cyan()
+            ));
+        }
+
+        if stale.is_empty() {
+            if untracked.is_empty() {
+                output.push_str(&format!("   {} All translations match the current {} text\n", "✅".green(), source_locale));
+            }
+            return output;
+        }
+
+        for (locale, fields) in stale {
+            let labels: Vec<&str> = fields.iter().map(|field| Self::field_label(field).1).collect();
+            output.push_str(&format!(
+                "   {} {} {:<8} {}\n",
+                "⚠️".yellow(),
+                locales::flag(locale),
+                locale,
+                format!("stale: {}", labels.join(", ")).yellow()
+            ));
+        }
+        // Pulled and reviewed translations are only replaced with --overwrite
+        let stale_locales: Vec<&str> = stale.keys().map(|locale| locale.as_str()).collect();
+        output.push_str(&format!(
+            "   {} changed since these were translated. Run {} to refresh them or {} to keep them.\n",
+            source_locale,
+            format!("rosetta-connect translate --overwrite --locales {}", stale_locales.join(",")).bright_cyan(),
+            "rosetta-connect review".bright_cyan()
+        ));
+        output
+    }
+
+    /// Which source fields will be translated for each locale, which are reused from the
+    /// translation memory, and which changed source fields are skipped because the translation
+    /// was edited locally
+    pub fn format_translation_plan(
+        pending: &BTreeMap<String, Vec<String>>,
+        kept: &BTreeMap<String, Vec<String>>,
+        from_memory: &BTreeMap<String, LocaleFields>,
+        references: &BTreeMap<String, BTreeMap<String, Vec<MemoryMatch>>>,
+    ) -> String {
+        let labels = |fields: &mut dyn Iterator<Item = &String>| fields.map(|field| Self::field_label(field).1).collect::<Vec<_>>().join(", ");
+        let mut output = format!("\n{}\n", "📋 Translation Plan".bold());
+        let mut any_kept = false;
+
+        for (locale, fields) in pending {
+            let edited = kept.get(locale).filter(|edited| !edited.is_empty());
+            let remembered = from_memory.get(locale).filter(|remembered| !remembered.is_empty());
+
+            let mut parts = Vec::new();
+            if !fields.is_empty() {
+                parts.push(labels(&mut fields.iter()));
+            }
+            if let Some(remembered) = remembered {
+                parts.push(format!("from memory: {}", labels(&mut remembered.keys())).cyan().to_string());
+            }
+            if let Some(edited) = edited {
+                any_kept = true;
+                parts.push(format!("keeping local edits: {}", labels(&mut edited.iter())).yellow().to_string());
+            }
+            let plan = match (parts.is_empty(), edited.is_some()) {
+                (false, _) => parts.join(" · "),
+                (true, true) => "nothing to translate".dimmed().to_string(),
+                (true, false) => "up to date".dimmed().to_string(),
+            };
+            output.push_str(&format!("   {} {:<8} {}\n", locales::flag(locale), locale, plan));
+        }
+
+        if any_kept {
+            output.push_str(&format!(
+                "{}  Their source text changed, but they were edited or reviewed locally. Use --overwrite to retranslate them.\n",
+                "⚠️".yellow()
+            ));
+        }
+        let reference_count: usize = references.values().flat_map(|fields| fields.values()).map(|matches| matches.len()).sum();
+        if reference_count > 0 {
+            output.push_str(&format!(
+                "🧠 {} similar approved translation(s) from the translation memory will guide the AI\n",
+                reference_count
+            ));
+        }
+        output.push('\n');
+        output
+    }
+
+    /// Summarize which translated fields were saved as drafts and which local edits were kept
+    pub fn format_saved_drafts(saved: &BTreeMap<String, BTreeMap<String, DraftOutcome>>, dir: &Path) -> String {
+        let mut output = format!("\n💾 Saved translations as drafts in {}\n", dir.display().to_string().cyan());
+        let mut any_kept = false;
+
+        for (locale, outcomes) in saved {
+            let fields_with = |wanted: DraftOutcome| -> Vec<&str> {
+                outcomes
+                    .iter()
+                    .filter(|(_, outcome)| **outcome == wanted)
+                    .map(|(field, _)| field.as_str())
+                    .collect()
+            };
+            let written = fields_with(DraftOutcome::Saved);
+            let unchanged = fields_with(DraftOutcome::Unchanged);
+            let kept = fields_with(DraftOutcome::KeptEdited);
+
+            let mut parts = vec![format!("{} saved", written.len()).green().to_string()];
+            if !unchanged.is_empty() {
+                parts.push(format!("{} unchanged", unchanged.len()).dimmed().to_string());
+            }
+            if !kept.is_empty() {
+                any_kept = true;
+                parts.push(format!("kept local edits: {}", kept.join(", ")).yellow().to_string());
+            }
+            output.push_str(&format!("   {} {:<8} {}\n", locales::flag(locale), locale, parts.join(" · ")));
+        }
+
+        if any_kept {
+            output.push_str(&format!(
+                "{}  Fields edited or reviewed locally were not replaced. Use --overwrite to replace them.\n",
+                "⚠️".yellow()
+            ));
+        }
+        output
+    }
+
+    /// Translations that came back over their character limit and the result of shortening them
+    pub fn format_length_fixes(fixes: &BTreeMap<String, Vec<LengthFix>>) -> String {
+        let mut output = format!("\n✂️  {}\n", "Length Limits".bold());
+        let mut any_unresolved = false;
+
+        for (locale, locale_fixes) in fixes {
+            for (index, fix) in locale_fixes.iter().enumerate() {
+                let (flag, code) = if index == 0 { (locales::flag(locale), locale.as_str()) } else { ("  ", "") };
+                let result = if fix.resolved() {
+                    format!("✓ shortened after {} retry(s)", fix.attempts).green().to_string()
                 } else {
-                    keywords.to_string()
+                    any_unresolved = true;
+                    "✗ needs manual review".red().to_string()
                 };
-                output.push_str(&format!("🔍 {}\n", preview));
+                output.push_str(&format!(
+                    "   {} {:<8} {:<18} {} → {}/{}  {}\n",
+                    flag,
+                    code,
+                    Self::field_label(&fix.field).1,
+                    fix.original,
+                    fix.length,
+                    fix.limit,
+                    result
+                ));
+            }
+        }
+
+        if any_unresolved {
+            output.push_str(&format!(
+                "{}  Drafts over the limit were saved as they are; shorten them by hand before pushing\n",
+                "⚠️".yellow()
+            ));
+        }
+        output
+    }
+
+    /// Glossary terms the AI translated or rendered differently, per locale
+    pub fn format_glossary_violations(violations: &BTreeMap<String, Vec<GlossaryViolation>>) -> String {
+        let mut output = format!("\n📖 {}\n", "Glossary Check".bold());
+
+        if violations.values().all(|found| found.is_empty()) {
+            output.push_str(&format!("   {} All glossary terms were preserved\n", "✅".green()));
+            return output;
+        }
+
+        for (locale, found) in violations {
+            if found.is_empty() {
+                output.push_str(&format!("   {} {:<8} {}\n", locales::flag(locale), locale, "ok".green()));
+                continue;
+            }
+            output.push_str(&format!(
+                "   {} {:<8} {}\n",
+                locales::flag(locale),
+                locale,
+                format!("{} violation(s)", found.len()).red()
+            ));
+            for violation in found {
+                let problem = match &violation.kind {
+                    ViolationKind::Translated => format!("\"{}\" must stay untranslated", violation.term),
+                    ViolationKind::WrongRendering { expected } => {
+                        format!("\"{}\" should be rendered as \"{}\"", violation.term, expected)
+                    }
+                };
+                output.push_str(&format!(
+                    "      {} {}: {}\n",
+                    "✗".red(),
+                    Self::field_label(&violation.field).1,
+                    problem
+                ));
+            }
+        }
+        output.push_str(&format!(
+            "{}  Drafts were saved as translated; fix them before running {}\n",
+            "⚠️".yellow(),
+            "rosetta-connect review".bright_cyan()
+        ));
+        output
+    }
+
+    /// Translations that break their locale's style guide, per locale
+    pub fn format_style_issues(issues: &BTreeMap<String, Vec<StyleIssue>>) -> String {
+        let mut output = format!("\n✒️  {}\n", "Style Check".bold());
+
+        for (locale, found) in issues {
+            if found.is_empty() {
+                output.push_str(&format!("   {} {:<8} {}\n", locales::flag(locale), locale, "ok".green()));
+                continue;
+            }
+            output.push_str(&format!(
+                "   {} {:<8} {}\n",
+                locales::flag(locale),
+                locale,
+                format!("{} issue(s)", found.len()).yellow()
+            ));
+            for issue in found {
+                output.push_str(&format!(
+                    "      {} {}: {}\n",
+                    "•".yellow(),
+                    Self::field_label(&issue.field).1,
+                    issue.message
+                ));
             }
         }
-        
         output
     }
     
+    /// Format per-locale, per-field upload results returned by `push`
+    /// Columns: Locale | Field | Result
+    pub fn format_push_results(results: &[Value]) -> String {
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("Locale").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Field").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Result").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+
+        for result in results {
+            let locale = result.get("locale").and_then(|v| v.as_str()).unwrap_or("");
+            let fields = result.get("fields").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+
+            for field in fields {
+                let name = field.get("field").and_then(|v| v.as_str()).unwrap_or("");
+                let success = field.get("success").and_then(|v| v.as_bool()).unwrap_or(false);
+                let outcome = if success {
+                    Cell::new("✅ Updated").fg(Color::Green)
+                } else {
+                    let error = field.get("error").and_then(|v| v.as_str()).unwrap_or("Unknown error");
+                    Cell::new(format!("❌ {}", error)).fg(Color::Red)
+                };
+
+                table.add_row(vec![
+                    Cell::new(format!("{} {}", locales::flag(locale), locale)),
+                    Cell::new(name),
+                    outcome,
+                ]);
+            }
+        }
+
+        format!("{}\n{}\n\n{}\n", "📤 Push Results".bold(), "—".repeat(18), table)
+    }
+
+    /// Format the field changes of a push plan
+    /// Columns: Locale | Field | Change | Remote → Local
+    pub fn format_push_plan(changes: &[FieldChange]) -> String {
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("Locale").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Field").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Change").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Remote → Local").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+
+        let preview = |text: &Option<String>| -> String {
+            match text {
+                Some(t) => truncate(t, 60),
+                None => "—".to_string(),
+            }
+        };
+
+        for change in changes {
+            let kind = match change.kind {
+                ChangeKind::Added => Cell::new("+ add").fg(Color::Green),
+                ChangeKind::Modified => Cell::new("~ modify").fg(Color::Yellow),
+                ChangeKind::Removed => Cell::new("- remove").fg(Color::Red),
+            };
+            table.add_row(vec![
+                Cell::new(format!("{} {}", locales::flag(&change.locale), change.locale)),
+                Cell::new(&change.field),
+                kind,
+                Cell::new(format!("{}\n→ {}", preview(&change.old), preview(&change.new))),
+            ]);
+        }
+
+        let locales: std::collections::BTreeSet<&str> = changes.iter().map(|c| c.locale.as_str()).collect();
+        format!(
+            "{}\n{}\n\n{}\n📊 {} field change(s) across {} locale(s)\n",
+            "📋 Push Plan".bold(),
+            "—".repeat(18),
+            table,
+            changes.len(),
+            locales.len()
+        )
+    }
+
+    /// Format field-level differences grouped by locale; long text fields get an inline word diff
+    pub fn format_field_diff(changes: &[FieldChange], old_label: &str, new_label: &str) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("{} ({} → {})\n", "🔍 Differences".bold(), old_label, new_label));
+        output.push_str(&"—".repeat(18));
+        output.push_str("\n\n");
+
+        if changes.is_empty() {
+            output.push_str(&format!("{}\n", "✅ No differences".green()));
+            return output;
+        }
+
+        let mut current_locale: Option<&str> = None;
+        for change in changes {
+            if current_locale != Some(change.locale.as_str()) {
+                if current_locale.is_some() {
+                    output.push('\n');
+                }
+                output.push_str(&format!("{} {}\n", locales::flag(&change.locale), change.locale.bold()));
+                current_locale = Some(change.locale.as_str());
+            }
+
+            let marker = match change.kind {
+                ChangeKind::Added => "+".green().to_string(),
+                ChangeKind::Removed => "-".red().to_string(),
+                ChangeKind::Modified => "~".yellow().to_string(),
+            };
+            output.push_str(&format!("   {} {} {}\n", marker, change.field, format!("({})", change.kind.label()).dimmed()));
+
+            let indent = "\n       ";
+            match (&change.old, &change.new) {
+                (Some(old), Some(new)) if LONG_TEXT_FIELDS.contains(&change.field.as_str()) => {
+                    let inline = Self::inline_word_diff(old, new).replace('\n', indent);
+                    output.push_str(&format!("       {}\n", inline));
+                }
+                (old, new) => {
+                    if let Some(old) = old {
+                        output.push_str(&format!("       {} {}\n", "-".red(), old.replace('\n', indent).red()));
+                    }
+                    if let Some(new) = new {
+                        output.push_str(&format!("       {} {}\n", "+".green(), new.replace('\n', indent).green()));
+                    }
+                }
+            }
+        }
+
+        let locales: std::collections::BTreeSet<&str> = changes.iter().map(|c| c.locale.as_str()).collect();
+        output.push_str(&format!(
+            "\n📊 Summary: {} field(s) differ across {} locale(s)\n",
+            changes.len(),
+            locales.len()
+        ));
+        output
+    }
+
+    /// Format screenshot set changes between two version snapshots
+    pub fn format_screenshot_diff(changes: &[ScreenshotChange]) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("\n{}\n", "🖼️  Screenshots".bold()));
+        output.push_str(&"—".repeat(18));
+        output.push_str("\n\n");
+
+        if changes.is_empty() {
+            output.push_str(&format!("{}\n", "✅ No screenshot changes".green()));
+            return output;
+        }
+
+        let mut current_set: Option<(&str, &str)> = None;
+        for change in changes {
+            let set = (change.locale.as_str(), change.display_type.as_str());
+            if current_set != Some(set) {
+                output.push_str(&format!("{} {} {}\n", locales::flag(&change.locale), change.locale.bold(), change.display_type.dimmed()));
+                current_set = Some(set);
+            }
+
+            let line = match change.kind {
+                ScreenshotChangeKind::Added => format!("+ {} (#{})", change.item, change.to_position.unwrap_or(0)).green().to_string(),
+                ScreenshotChangeKind::Removed => format!("- {} (was #{})", change.item, change.from_position.unwrap_or(0)).red().to_string(),
+                ScreenshotChangeKind::Moved => format!(
+                    "↕ {} (#{} → #{})",
+                    change.item,
+                    change.from_position.unwrap_or(0),
+                    change.to_position.unwrap_or(0)
+                ).yellow().to_string(),
+            };
+            output.push_str(&format!("   {}\n", line));
+        }
+
+        output.push_str(&format!("\n📊 {} screenshot change(s)\n", changes.len()));
+        output
+    }
+
+    /// Word-level inline diff showing only the changed regions with a little context.
+    /// Uses Unicode word boundaries so CJK text is compared per word rather than per paragraph.
+    fn inline_word_diff(old: &str, new: &str) -> String {
+        let diff = TextDiff::from_unicode_words(old, new);
+        let ops = diff.ops();
+        let mut output = String::new();
+
+        let groups = diff.grouped_ops(8);
+        let old_len = ops.last().map(|op| op.old_range().end).unwrap_or(0);
+
+        for (index, group) in groups.iter().enumerate() {
+            let starts_at_beginning = group.first().map(|op| op.old_range().start == 0).unwrap_or(true);
+            if index > 0 || !starts_at_beginning {
+                output.push_str(&"…".dimmed().to_string());
+            }
+
+            for op in group {
+                for change in diff.iter_changes(op) {
+                    let value = change.value();
+                    match change.tag() {
+                        ChangeTag::Equal => output.push_str(value),
+                        ChangeTag::Delete => output.push_str(&value.red().strikethrough().to_string()),
+                        ChangeTag::Insert => output.push_str(&value.green().underline().to_string()),
+                    }
+                }
+            }
+        }
+
+        let ends_at_end = groups
+            .last()
+            .and_then(|group| group.last())
+            .map(|op| op.old_range().end >= old_len)
+            .unwrap_or(true);
+        if !ends_at_end {
+            output.push_str(&"…".dimmed().to_string());
+        }
+
+        output
+    }
+
+    /// Format validation diagnostics as a table with a summary line
+    /// Columns: Severity | Locale | Field | Message
+    pub fn format_diagnostics(diagnostics: &[Diagnostic], locale_count: usize) -> String {
+        let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+        let warnings = diagnostics.iter().filter(|d| d.severity == Severity::Warning).count();
+
+        let mut output = format!("{}\n{}\n\n", "🔍 Validation Results".bold(), "—".repeat(18));
+
+        if diagnostics.is_empty() {
+            output.push_str(&format!("{}\n", "✅ All locales pass App Store checks".green()));
+        } else {
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_content_arrangement(ContentArrangement::Dynamic);
+            table.set_header(vec![
+                Cell::new("Severity").add_attribute(comfy_table::Attribute::Bold),
+                Cell::new("Locale").add_attribute(comfy_table::Attribute::Bold),
+                Cell::new("Field").add_attribute(comfy_table::Attribute::Bold),
+                Cell::new("Message").add_attribute(comfy_table::Attribute::Bold),
+            ]);
+
+            for diagnostic in diagnostics {
+                let severity = match diagnostic.severity {
+                    Severity::Error => Cell::new("❌ error").fg(Color::Red),
+                    Severity::Warning => Cell::new("⚠️ warning").fg(Color::Yellow),
+                    Severity::Info => Cell::new("ℹ️ info").fg(Color::Blue),
+                };
+                table.add_row(vec![
+                    severity,
+                    Cell::new(format!("{} {}", locales::flag(&diagnostic.locale), diagnostic.locale)),
+                    Cell::new(diagnostic.field.as_deref().unwrap_or("—")),
+                    Cell::new(&diagnostic.message),
+                ]);
+            }
+            output.push_str(&format!("{}\n", table));
+        }
+
+        let status = if errors > 0 {
+            "Not ready for submission".red().to_string()
+        } else {
+            "Ready for App Store submission".green().to_string()
+        };
+        output.push_str(&format!(
+            "\n📊 Summary: {} locale(s), {} error(s), {} warning(s) — {}\n",
+            locale_count, errors, warnings, status
+        ));
+        output
+    }
+
     /// Format cost information in a structured way
     pub fn format_cost_info(cost_data: &Value) -> String {
         let mut output = String::new();
@@ -496,4 +816,375 @@ impl DataFormatter {
         output.push('\n');
         output
     }
-}
\ No newline at end of file
+
+    /// Format the snapshot history, newest first
+    /// Columns: Snapshot | Taken | Source | Version | Locales | Fields | Screenshots
+    pub fn format_history(snapshots: &[Snapshot]) -> String {
+        let mut output = format!("{}\n{}\n\n", "🗂️  Snapshot History".bold(), "—".repeat(18));
+
+        if snapshots.is_empty() {
+            output.push_str("No snapshots yet. Snapshots are recorded on every 'pull' and successful 'push'.\n");
+            return output;
+        }
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("Snapshot").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Taken").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Source").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Version").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Locales").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Fields").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Screenshots").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+
+        for snapshot in snapshots {
+            let source = match snapshot.source {
+                SnapshotSource::Pull => Cell::new("⬇️ pull").fg(Color::Blue),
+                SnapshotSource::Push => Cell::new("⬆️ push").fg(Color::Green),
+                SnapshotSource::Rollback => Cell::new("↩️ rollback").fg(Color::Yellow),
+            };
+            table.add_row(vec![
+                Cell::new(&snapshot.id),
+                Cell::new(snapshot.created_at.format("%Y-%m-%d %H:%M")),
+                source,
+                Cell::new(snapshot.version.as_deref().unwrap_or("—")),
+                Cell::new(snapshot.locales.len()),
+                Cell::new(snapshot.field_count()),
+                Cell::new(snapshot.screenshots.len()),
+            ]);
+        }
+
+        output.push_str(&format!("{}\n", table));
+        output.push_str("\n💡 Restore one with: rosetta-connect rollback <snapshot|version>\n");
+        output
+    }
+
+    /// Format recorded AI spend grouped by month, app or locale, with a total row
+    pub fn format_cost_report(rows: &[SpendRow], group: ReportGroup) -> String {
+        let mut output = format!("{}\n{}\n\n", "💵 AI Spend".bold(), "—".repeat(11));
+
+        if rows.is_empty() {
+            output.push_str("Nothing spent yet. Every 'translate' run that calls the AI is recorded in .rosetta/ledger.jsonl.\n");
+            return output;
+        }
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new(group.label()).add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Runs").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Input tokens").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Output tokens").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Cost").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+
+        for row in rows {
+            let key = match group {
+                ReportGroup::Locale => format!("{} {}", locales::flag(&row.key), row.key),
+                _ => row.key.clone(),
+            };
+            table.add_row(vec![
+                Cell::new(key),
+                Cell::new(row.runs),
+                Cell::new(row.input_tokens),
+                Cell::new(row.output_tokens),
+                Cell::new(format!("${:.4}", row.cost)),
+            ]);
+        }
+
+        // A run touching several locales counts once per locale, so only tokens and cost add up
+        table.add_row(vec![
+            Cell::new("Total").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new(""),
+            Cell::new(rows.iter().map(|row| row.input_tokens).sum::<u64>()),
+            Cell::new(rows.iter().map(|row| row.output_tokens).sum::<u64>()),
+            Cell::new(format!("${:.4}", rows.iter().map(|row| row.cost).sum::<f64>())).add_attribute(comfy_table::Attribute::Bold),
+        ]);
+
+        output.push_str(&format!("{}\n", table));
+        output
+    }
+
+    /// Format template evaluation scores, one row per version, with the change from the first
+    /// version to the last when there are two
+    pub fn format_template_eval(name: &str, versions: &[(String, EvalScores)]) -> String {
+        let title = format!("🧪 Template Evaluation: {}", name);
+        let mut output = format!("{}\n{}\n\n", title.bold(), "—".repeat(title.chars().count()));
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("Version").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Within limit").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Glossary kept").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Similarity").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Tokens").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Cost").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+
+        for (label, scores) in versions {
+            let glossary = match scores.glossary_rate() {
+                Some(rate) => format!("{}/{} ({:.0}%)", scores.glossary_kept, scores.glossary_cases, rate * 100.0),
+                None => "-".to_string(),
+            };
+            table.add_row(vec![
+                Cell::new(label),
+                Cell::new(format!("{}/{} ({:.0}%)", scores.within_limit, scores.outputs, scores.limit_rate() * 100.0)),
+                Cell::new(glossary),
+                Cell::new(format!("{:.2}", scores.similarity)),
+                Cell::new(scores.usage.input + scores.usage.output),
+                Cell::new(format!("${:.4}", scores.cost)),
+            ]);
+        }
+
+        if let [(_, before), (_, after)] = versions {
+            // Higher is better for the scores, lower for tokens and cost
+            let change = |delta: f64, text: String, higher_is_better: bool| {
+                let cell = Cell::new(text);
+                if delta.abs() < f64::EPSILON {
+                    cell
+                } else if (delta > 0.0) == higher_is_better {
+                    cell.fg(Color::Green)
+                } else {
+                    cell.fg(Color::Red)
+                }
+            };
+            let limit = (after.limit_rate() - before.limit_rate()) * 100.0;
+            let glossary = match (before.glossary_rate(), after.glossary_rate()) {
+                (Some(before), Some(after)) => {
+                    let delta = (after - before) * 100.0;
+                    change(delta, format!("{:+.0} pts", delta), true)
+                }
+                _ => Cell::new("-"),
+            };
+            let similarity = after.similarity - before.similarity;
+            let tokens = (after.usage.input + after.usage.output) as f64 - (before.usage.input + before.usage.output) as f64;
+            let cost = after.cost - before.cost;
+            table.add_row(vec![
+                Cell::new("Change").add_attribute(comfy_table::Attribute::Bold),
+                change(limit, format!("{:+.0} pts", limit), true),
+                glossary,
+                change(similarity, format!("{:+.2}", similarity), true),
+                change(tokens, format!("{:+}", tokens), false),
+                change(cost, format!("{:+.4}", cost), false),
+            ]);
+        }
+
+        output.push_str(&format!("{}\n", table));
+        output
+    }
+
+    /// Format what one template version produced for each evaluation case, flagging outputs over
+    /// the field limit or missing glossary terms
+    pub fn format_eval_outputs(label: &str, outputs: &[EvalOutput], glossary: &GlossaryConfig) -> String {
+        let mut output = format!("{}\n", format!("📝 Outputs ({})", label).bold());
+
+        for result in outputs {
+            let mut flags = Vec::new();
+            if !result.within_limit() {
+                let limit = field_limit(&result.field).unwrap_or_default();
+                flags.push(format!("{}/{} chars", char_count(&result.output), limit));
+            }
+            if result.glossary_kept(glossary) == Some(false) {
+                flags.push("glossary term missing".to_string());
+            }
+            let flags = if flags.is_empty() {
+                String::new()
+            } else {
+                format!(" ⚠️  {}", flags.join(", ")).yellow().to_string()
+            };
+
+            output.push_str(&format!(
+                "\n{} {} {} (similarity {:.2}){}\n",
+                locales::flag(&result.locale),
+                result.locale,
+                result.field,
+                result.similarity(),
+                flags
+            ));
+            // Continuation lines of multi-line text stay aligned under the first
+            let indent = |text: &str| truncate(text, 160).replace('\n', "\n             ");
+            output.push_str(&format!("  {} {}\n", "output:   ".dimmed(), indent(&result.output)));
+            output.push_str(&format!("  {} {}\n", "reference:".dimmed(), indent(&result.reference)));
+        }
+
+        output.push('\n');
+        output
+    }
+
+    /// Format a locale's local listing as it will be submitted: every field with its character count
+    /// against the App Store limit, screenshots per display type with pixel dimensions, and warnings
+    pub fn format_preview(locale: &str, version: Option<&str>, fields: &LocaleFields, screenshots: &[ScreenshotSet]) -> String {
+        let mut output = String::new();
+        let mut warnings: Vec<String> = Vec::new();
+
+        let title = match version {
+            Some(version) => format!("{} {} Preview (version {})", locales::flag(locale), locale, version),
+            None => format!("{} {} Preview", locales::flag(locale), locale),
+        };
+        output.push_str(&format!("{}\n{}\n", title.bold(), "═".repeat(50)));
+
+        for field in METADATA_FIELDS {
+            let (icon, label) = Self::field_label(field);
+            let text = fields.get(field).map(|v| v.trim()).unwrap_or("");
+            let limit = field_limit(field).unwrap_or(usize::MAX);
+            let count = char_count(text);
+
+            let counter = format!("{} / {}", count, limit);
+            let counter = if count > limit { counter.red().to_string() } else { counter.dimmed().to_string() };
+            output.push_str(&format!("\n{} {} ({})\n", icon, label.bold(), counter));
+
+            if text.is_empty() {
+                output.push_str(&format!("   {}\n", "— empty".dimmed()));
+                continue;
+            }
+            for line in wrap(text, 72) {
+                output.push_str(&format!("   {}\n", line));
+            }
+
+            if count > limit {
+                warnings.push(format!(
+                    "{} is {} character(s) over the {} character limit and will be rejected; only this part fits: \"{}\"",
+                    label,
+                    count - limit,
+                    limit,
+                    prefix(text, limit)
+                ));
+            }
+        }
+
+        output.push_str(&format!("\n🖼️  {}\n", "Screenshots".bold()));
+        if screenshots.is_empty() {
+            output.push_str(&format!("   {}\n", "No screenshot manifest for this locale".dimmed()));
+        }
+        for set in screenshots {
+            output.push_str(&format!("   {} ({})\n", set.display_type, set.items.len()));
+            for (index, item) in set.items.iter().enumerate() {
+                let name = item.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let size = match item.dimensions {
+                    Some((width, height)) => format!("{}×{}", width, height).dimmed().to_string(),
+                    None => {
+                        warnings.push(format!("{} {}: {} is missing or not a readable image", set.display_type, index + 1, name));
+                        "unreadable".red().to_string()
+                    }
+                };
+                output.push_str(&format!("     {}. {}  {}\n", index + 1, name, size));
+            }
+            if set.items.len() > 10 {
+                warnings.push(format!("{} has {} screenshots; App Store Connect accepts at most 10", set.display_type, set.items.len()));
+            }
+        }
+
+        output.push_str(&format!("\n📊 {}\n", "Content Stats".bold()));
+        let screenshot_total: usize = screenshots.iter().map(|set| set.items.len()).sum();
+        let filled = METADATA_FIELDS.iter().filter(|f| fields.get(**f).is_some_and(|v| !v.trim().is_empty())).count();
+        output.push_str(&format!("   • Fields: {} / {} filled\n", filled, METADATA_FIELDS.len()));
+        output.push_str(&format!("   • Screenshots: {} in {} display type(s)\n", screenshot_total, screenshots.len()));
+
+        if warnings.is_empty() {
+            output.push_str(&format!("\n{}\n", "✅ Everything fits within App Store limits".green()));
+        } else {
+            output.push('\n');
+            for warning in &warnings {
+                output.push_str(&format!("{} {}\n", "⚠️ ".yellow(), warning));
+            }
+        }
+
+        output
+    }
+
+    /// Draw the simulated search result card and product page of every device profile
+    pub fn format_product_page(locale: &str, renders: &[PageRender]) -> String {
+        let mut output = format!(
+            "{}\n{}\n",
+            format!("{} {} App Store Simulation", locales::flag(locale), locale).bold(),
+            "═".repeat(50)
+        );
+
+        for render in renders {
+            let device = &render.device;
+            output.push_str(&format!("\n📱 {} {}\n", device.name.bold(), format!("({}pt)", device.points).dimmed()));
+
+            output.push_str(&format!("   {}\n", "Search result".dimmed()));
+            let card_width = device.search_title_columns.max(device.search_subtitle_columns);
+            let mut card = Vec::new();
+            card.extend(render.search_title.lines.iter().map(|l| l.bold().to_string()));
+            card.extend(render.search_subtitle.lines.iter().map(|l| l.dimmed().to_string()));
+            output.push_str(&Self::draw_box(&card, card_width));
+
+            output.push_str(&format!("   {}\n", "Product page".dimmed()));
+            let mut page = Vec::new();
+            page.extend(render.page_title.lines.iter().map(|l| l.bold().to_string()));
+            page.extend(render.page_subtitle.lines.iter().map(|l| l.dimmed().to_string()));
+            if !render.promotional_text.lines.is_empty() {
+                page.push(String::new());
+                page.extend(render.promotional_text.lines.iter().cloned());
+            }
+            page.push(String::new());
+            page.extend(Self::folded_lines(&render.description, device.body_columns));
+            page.push(String::new());
+            page.push("What's New".bold().to_string());
+            page.extend(Self::folded_lines(&render.whats_new, device.body_columns));
+            output.push_str(&Self::draw_box(&page, device.body_columns));
+
+            for warning in render.warnings() {
+                output.push_str(&format!("   {} {}\n", "⚠️ ".yellow(), warning));
+            }
+        }
+
+        output
+    }
+
+    /// Folded text with the App Store's "more" button right-aligned on the last visible line
+    fn folded_lines(clipped: &Clipped, width: usize) -> Vec<String> {
+        if clipped.lines.is_empty() {
+            return vec!["—".dimmed().to_string()];
+        }
+
+        let mut lines = clipped.lines.clone();
+        if clipped.truncated {
+            if let Some(last) = lines.last_mut() {
+                let room = width.saturating_sub(display_width("more") + 1);
+                let text = truncate_to_width(last.trim_end_matches('…'), room.saturating_sub(1));
+                let text = if text.ends_with('…') { text } else { format!("{}…", text) };
+                *last = format!("{} {}", pad_to_width(&text, room), "more".blue());
+            }
+        }
+        lines
+    }
+
+    /// Draw lines inside a box `width` columns wide; lines may contain ANSI styling
+    fn draw_box(lines: &[String], width: usize) -> String {
+        let mut output = format!("   ┌{}┐\n", "─".repeat(width + 2));
+        for line in lines {
+            let visible = display_width(&strip_ansi(line));
+            output.push_str(&format!("   │ {}{} │\n", line, " ".repeat(width.saturating_sub(visible))));
+        }
+        output.push_str(&format!("   └{}┘\n", "─".repeat(width + 2)));
+        output
+    }
+}
+
+/// Remove ANSI escape sequences so styled text can be measured
+fn strip_ansi(text: &str) -> String {
+    let mut output = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip to the final byte of the CSI sequence
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
@@ -0,0 +1,135 @@
+use crate::config::GlossaryConfig;
+use crate::workspace::LocaleFields;
+use std::collections::BTreeMap;
+
+/// How a translated field broke the glossary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// A protected term is missing or was altered
+    Translated,
+    /// A product term was not rendered with its fixed translation
+    WrongRendering { expected: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct GlossaryViolation {
+    pub field: String,
+    pub term: String,
+    pub kind: ViolationKind,
+}
+
+pub fn is_empty(glossary: &GlossaryConfig) -> bool {
+    glossary.do_not_translate.is_empty() && glossary.terms.is_empty()
+}
+
+/// Fixed renderings for one locale, keyed by source term
+pub fn terms_for<'a>(glossary: &'a GlossaryConfig, locale: &str) -> BTreeMap<&'a str, &'a str> {
+    glossary.terms
+        .iter()
+        .filter_map(|(term, renderings)| renderings.get(locale).map(|rendering| (term.as_str(), rendering.as_str())))
+        .collect()
+}
+
+/// Protected terms occurring in a source text, for its translation prompt
+pub fn protected_in(glossary: &GlossaryConfig, text: &str) -> Vec<String> {
+    glossary.do_not_translate.iter().filter(|term| text.contains(term.as_str())).cloned().collect()
+}
+
+/// Fixed renderings in `locale` of the terms occurring in a source text, for its translation prompt
+pub fn renderings_in(glossary: &GlossaryConfig, locale: &str, text: &str) -> BTreeMap<String, String> {
+    terms_for(glossary, locale)
+        .into_iter()
+        .filter(|(term, _)| contains_ignore_case(text, term))
+        .map(|(term, rendering)| (term.to_string(), rendering.to_string()))
+        .collect()
+}
+
+/// Check translated fields of one locale against the source fields they came from
+pub fn check(glossary: &GlossaryConfig, locale: &str, source: &LocaleFields, translated: &LocaleFields) -> Vec<GlossaryViolation> {
+    let renderings = terms_for(glossary, locale);
+    let mut violations = Vec::new();
+
+    for (field, text) in translated {
+        let Some(source_text) = source.get(field) else {
+            continue;
+        };
+
+        // Protected terms must survive exactly as written
+        for term in &glossary.do_not_translate {
+            if source_text.contains(term.as_str()) && !text.contains(term.as_str()) {
+                violations.push(GlossaryViolation {
+                    field: field.clone(),
+                    term: term.clone(),
+                    kind: ViolationKind::Translated,
+                });
+            }
+        }
+
+        for (term, rendering) in &renderings {
+            if contains_ignore_case(source_text, term) && !contains_ignore_case(text, rendering) {
+                violations.push(GlossaryViolation {
+                    field: field.clone(),
+                    term: term.to_string(),
+                    kind: ViolationKind::WrongRendering { expected: rendering.to_string() },
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+fn contains_ignore_case(text: &str, term: &str) -> bool {
+    text.to_lowercase().contains(&term.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> LocaleFields {
+        pairs.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect()
+    }
+
+    fn glossary() -> GlossaryConfig {
+        GlossaryConfig {
+            do_not_translate: vec!["Hands Time".to_string(), "iCloud".to_string()],
+            terms: BTreeMap::from([(
+                "widget".to_string(),
+                BTreeMap::from([("fr-FR".to_string(), "widget".to_string()), ("de-DE".to_string(), "Widget".to_string())]),
+            )]),
+        }
+    }
+
+    #[test]
+    fn prompt_terms_are_those_in_the_source_text() {
+        let glossary = glossary();
+        assert_eq!(protected_in(&glossary, "Hands Time syncs with iCloud"), ["Hands Time", "iCloud"]);
+        assert!(protected_in(&glossary, "hands time").is_empty());
+        assert_eq!(renderings_in(&glossary, "de-DE", "Add a Widget"), BTreeMap::from([("widget".to_string(), "Widget".to_string())]));
+        assert!(renderings_in(&glossary, "ja", "Add a widget").is_empty());
+    }
+
+    #[test]
+    fn protected_terms_must_survive_exactly() {
+        let source = fields(&[("name", "Hands Time"), ("subtitle", "Synced with iCloud")]);
+        let translated = fields(&[("name", "Hands Time"), ("subtitle", "Synchronisé avec icloud"), ("keywords", "iCloud")]);
+
+        let violations = check(&glossary(), "fr-FR", &source, &translated);
+        assert_eq!(violations.len(), 1);
+        assert_eq!((violations[0].field.as_str(), violations[0].term.as_str()), ("subtitle", "iCloud"));
+        assert_eq!(violations[0].kind, ViolationKind::Translated);
+    }
+
+    #[test]
+    fn terms_must_use_their_rendering_ignoring_case() {
+        let source = fields(&[("description", "Add a Widget"), ("whatsNew", "New widgets")]);
+        let translated = fields(&[("description", "Ajoutez un WIDGET"), ("whatsNew", "Nouveaux gadgets")]);
+
+        let violations = check(&glossary(), "fr-FR", &source, &translated);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "whatsNew");
+        assert_eq!(violations[0].kind, ViolationKind::WrongRendering { expected: "widget".to_string() });
+        assert!(check(&glossary(), "ja", &source, &translated).is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+use crate::config::{Config, HistoryConfig};
+use crate::workspace::{content_hash, LocaleFields, Workspace};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// What produced a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotSource {
+    Pull,
+    Push,
+    /// The workspace as it was right before a rollback overwrote it
+    Rollback,
+}
+
+impl SnapshotSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnapshotSource::Pull => "pull",
+            SnapshotSource::Push => "push",
+            SnapshotSource::Rollback => "rollback",
+        }
+    }
+}
+
+/// An immutable copy of every locale's metadata and screenshot manifest at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub source: SnapshotSource,
+    pub app_id: String,
+    /// App Store version the content belongs to
+    pub version: Option<String>,
+    pub locales: BTreeMap<String, LocaleFields>,
+    /// `screenshots.json` manifest per locale
+    #[serde(default)]
+    pub screenshots: BTreeMap<String, Value>,
+}
+
+impl Snapshot {
+    pub fn new(
+        source: SnapshotSource,
+        app_id: &str,
+        version: Option<String>,
+        locales: BTreeMap<String, LocaleFields>,
+        screenshots: BTreeMap<String, Value>,
+    ) -> Self {
+        let created_at = Utc::now();
+        let content = serde_json::to_string(&(&locales, &screenshots)).unwrap_or_default();
+        let hash = content_hash(&format!("{}{}", created_at.to_rfc3339(), content));
+
+        Self {
+            id: format!("{}-{}", created_at.format("%Y%m%d-%H%M%S"), &hash[..8]),
+            created_at,
+            source,
+            app_id: app_id.to_string(),
+            version,
+            locales,
+            screenshots,
+        }
+    }
+
+    /// Capture the local workspace, with the screenshot manifests of the version it was pulled from
+    pub fn from_workspace(source: SnapshotSource, config: &Config, workspace: &Workspace, version: Option<String>) -> Result<Self> {
+        let locales = workspace.read_all()
+            .context("Failed to read local workspace")?;
+        let screenshots = Self::read_manifests(workspace, workspace.pulled_version()?.as_deref(), &locales)?;
+        Ok(Self::new(source, &config.app.bundle_id, version, locales, screenshots))
+    }
+
+    /// Screenshot manifests of `locales` in a downloaded version
+    pub fn read_manifests(
+        workspace: &Workspace,
+        version: Option<&str>,
+        locales: &BTreeMap<String, LocaleFields>,
+    ) -> Result<BTreeMap<String, Value>> {
+        let mut manifests = BTreeMap::new();
+        if let Some(version) = version {
+            for locale in locales.keys() {
+                if let Some(manifest) = workspace.read_screenshot_manifest(version, locale)? {
+                    manifests.insert(locale.clone(), manifest);
+                }
+            }
+        }
+        Ok(manifests)
+    }
+
+    /// Number of non-empty text fields across all locales
+    pub fn field_count(&self) -> usize {
+        self.locales
+            .values()
+            .map(|fields| fields.values().filter(|v| !v.trim().is_empty()).count())
+            .sum()
+    }
+}
+
+/// Snapshot files of one app under `.rosetta/history/<bundle_id>/`, one JSON file per snapshot
+pub struct HistoryStore {
+    dir: PathBuf,
+    retention: HistoryConfig,
+}
+
+impl HistoryStore {
+    pub fn open(config: &Config) -> Result<Self> {
+        let mut dir = std::env::current_dir()
+            .context("Failed to get current directory")?;
+        dir.push(".rosetta");
+        dir.push("history");
+        dir.push(&config.app.bundle_id);
+
+        Ok(Self {
+            dir,
+            retention: config.history.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Write a new snapshot, then apply the retention settings.
+    /// Returns the IDs of snapshots pruned to make room.
+    pub fn record(&self, snapshot: &Snapshot) -> Result<Vec<String>> {
+        self.write(snapshot)?;
+        self.prune()
+    }
+
+    /// Write a new snapshot without pruning, for callers that still need an older snapshot
+    /// retention could delete
+    pub fn write(&self, snapshot: &Snapshot) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create history directory: {}", self.dir.display()))?;
+
+        let path = self.snapshot_path(&snapshot.id);
+        let content = serde_json::to_string_pretty(snapshot)
+            .context("Failed to serialize snapshot")?;
+
+        // Snapshots are never rewritten once recorded
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .with_context(|| format!("Failed to create snapshot file: {}", path.display()))?;
+        file.write_all(content.as_bytes())
+            .with_context(|| format!("Failed to write snapshot file: {}", path.display()))
+    }
+
+    /// Every recorded snapshot, newest first
+    pub fn list(&self) -> Result<Vec<Snapshot>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read history directory: {}", self.dir.display()))?
+        {
+            let path = entry.context("Failed to read history entry")?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read snapshot: {}", path.display()))?;
+            let snapshot: Snapshot = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse snapshot: {}", path.display()))?;
+            snapshots.push(snapshot);
+        }
+
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+        Ok(snapshots)
+    }
+
+    /// Find a snapshot by ID, App Store version (newest snapshot of it) or unique ID prefix
+    pub fn find(&self, target: &str) -> Result<Snapshot> {
+        let snapshots = self.list()?;
+        if snapshots.is_empty() {
+            anyhow::bail!("No snapshots recorded yet. Snapshots are taken on every 'pull' and 'push'.");
+        }
+
+        if let Some(snapshot) = snapshots.iter().find(|s| s.id == target) {
+            return Ok(snapshot.clone());
+        }
+        // A version means what was pulled or pushed for it, not a pre-rollback backup
+        let of_version: Vec<&Snapshot> = snapshots.iter().filter(|s| s.version.as_deref() == Some(target)).collect();
+        if let Some(snapshot) = of_version.iter().find(|s| s.source != SnapshotSource::Rollback).or(of_version.first()) {
+            return Ok((*snapshot).clone());
+        }
+
+        let matches: Vec<&Snapshot> = snapshots.iter().filter(|s| s.id.starts_with(target)).collect();
+        match matches.as_slice() {
+            [snapshot] => Ok((*snapshot).clone()),
+            [] => anyhow::bail!("No snapshot or version matches '{}'. Run 'rosetta-connect history' to list snapshots.", target),
+            _ => anyhow::bail!("'{}' matches {} snapshots, use a longer ID", target, matches.len()),
+        }
+    }
+
+    /// Delete snapshots beyond `keep_last` or older than `max_age_days`; the newest is always kept
+    pub fn prune(&self) -> Result<Vec<String>> {
+        let cutoff = self.retention.max_age_days.map(|days| Utc::now() - Duration::days(i64::from(days)));
+
+        let mut pruned = Vec::new();
+        for (index, snapshot) in self.list()?.iter().enumerate() {
+            let too_many = index >= self.retention.keep_last.max(1);
+            let too_old = index > 0 && cutoff.is_some_and(|cutoff| snapshot.created_at < cutoff);
+            if too_many || too_old {
+                let path = self.snapshot_path(&snapshot.id);
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to delete snapshot: {}", path.display()))?;
+                pruned.push(snapshot.id.clone());
+            }
+        }
+        Ok(pruned)
+    }
+
+    fn snapshot_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(name: &str, keep_last: usize, max_age_days: Option<u32>) -> HistoryStore {
+        let dir = std::env::temp_dir().join(format!("rosetta-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        HistoryStore { dir, retention: HistoryConfig { keep_last, max_age_days } }
+    }
+
+    /// Record a snapshot taken `days_ago` days ago with a fixed ID
+    fn record(store: &HistoryStore, id: &str, source: SnapshotSource, version: &str, days_ago: i64) {
+        let mut snapshot = Snapshot::new(source, "com.example.app", Some(version.to_string()), BTreeMap::new(), BTreeMap::new());
+        snapshot.id = id.to_string();
+        snapshot.created_at = Utc::now() - Duration::days(days_ago);
+        store.write(&snapshot).unwrap();
+    }
+
+    fn ids(snapshots: &[Snapshot]) -> Vec<&str> {
+        snapshots.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn find_by_id_version_and_unique_prefix() {
+        let store = store("find", 50, None);
+        record(&store, "20260101-090000-aaaa1111", SnapshotSource::Pull, "1.0", 30);
+        record(&store, "20260201-090000-bbbb2222", SnapshotSource::Push, "1.1", 20);
+        record(&store, "20260202-090000-cccc3333", SnapshotSource::Rollback, "1.1", 10);
+
+        assert_eq!(store.find("20260101-090000-aaaa1111").unwrap().version.as_deref(), Some("1.0"));
+        // A version finds what was pushed for it rather than the newer pre-rollback backup
+        assert_eq!(store.find("1.1").unwrap().id, "20260201-090000-bbbb2222");
+        assert_eq!(store.find("20260202").unwrap().id, "20260202-090000-cccc3333");
+
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    #[test]
+    fn ambiguous_prefixes_and_unknown_targets_are_errors() {
+        let store = store("ambiguous", 50, None);
+        assert!(store.find("1.0").unwrap_err().to_string().starts_with("No snapshots recorded yet"));
+
+        record(&store, "20260201-090000-aaaa1111", SnapshotSource::Pull, "1.0", 2);
+        record(&store, "20260201-100000-bbbb2222", SnapshotSource::Push, "1.0", 1);
+
+        assert_eq!(store.find("202602").unwrap_err().to_string(), "'202602' matches 2 snapshots, use a longer ID");
+        assert!(store.find("2.0").unwrap_err().to_string().starts_with("No snapshot or version matches '2.0'"));
+
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_snapshots() {
+        let store = store("keep-last", 2, None);
+        record(&store, "a", SnapshotSource::Pull, "1.0", 3);
+        record(&store, "b", SnapshotSource::Pull, "1.0", 2);
+        record(&store, "c", SnapshotSource::Push, "1.0", 1);
+
+        assert_eq!(store.prune().unwrap(), ["a"]);
+        assert_eq!(ids(&store.list().unwrap()), ["c", "b"]);
+
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    #[test]
+    fn prune_by_age_keeps_recent_snapshots() {
+        let store = store("max-age", 50, Some(7));
+        record(&store, "old", SnapshotSource::Pull, "1.0", 30);
+        record(&store, "older", SnapshotSource::Pull, "1.0", 40);
+        record(&store, "recent", SnapshotSource::Push, "1.0", 1);
+
+        assert_eq!(store.prune().unwrap(), ["old", "older"]);
+        assert_eq!(ids(&store.list().unwrap()), ["recent"]);
+
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    #[test]
+    fn prune_never_deletes_the_newest_snapshot() {
+        let store = store("newest", 0, Some(7));
+        record(&store, "stale", SnapshotSource::Pull, "1.0", 30);
+
+        assert!(store.prune().unwrap().is_empty());
+        assert_eq!(ids(&store.list().unwrap()), ["stale"]);
+
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+}
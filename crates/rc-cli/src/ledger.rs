@@ -0,0 +1,270 @@
+use crate::provider::TokenUsage;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// What one `translate` or `template eval` run spent on one locale
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleSpend {
+    pub fields: Vec<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost: f64,
+}
+
+/// Spend of the run in progress, as reported by the provider
+#[derive(Debug, Clone, Default)]
+pub struct RunSpend {
+    pub locales: BTreeMap<String, LocaleSpend>,
+}
+
+impl RunSpend {
+    pub fn add(&mut self, locale: &str, field: &str, usage: TokenUsage, cost: f64) {
+        let spend = self.locales.entry(locale.to_string()).or_default();
+        if !spend.fields.iter().any(|f| f == field) {
+            spend.fields.push(field.to_string());
+        }
+        spend.input_tokens += usage.input;
+        spend.output_tokens += usage.output;
+        spend.cost += cost;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locales.is_empty()
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.locales.values().map(|spend| spend.cost).sum()
+    }
+
+    pub fn usage(&self) -> TokenUsage {
+        TokenUsage {
+            input: self.locales.values().map(|spend| spend.input_tokens).sum(),
+            output: self.locales.values().map(|spend| spend.output_tokens).sum(),
+        }
+    }
+}
+
+/// One `translate` or `template eval` run in `.rosetta/ledger.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntry {
+    pub recorded_at: DateTime<Utc>,
+    pub app_id: String,
+    pub provider: String,
+    pub model: String,
+    pub locales: BTreeMap<String, LocaleSpend>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost: f64,
+    /// False when the model had no known price and `cost` is 0
+    pub priced: bool,
+}
+
+impl LedgerEntry {
+    pub fn new(app_id: &str, provider: &str, model: &str, spend: RunSpend, priced: bool) -> Self {
+        let usage = spend.usage();
+        Self {
+            recorded_at: Utc::now(),
+            app_id: app_id.to_string(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            input_tokens: usage.input,
+            output_tokens: usage.output,
+            cost: spend.cost(),
+            locales: spend.locales,
+            priced,
+        }
+    }
+
+    /// Calendar month in UTC, e.g. "2025-03"
+    pub fn month(&self) -> String {
+        self.recorded_at.format("%Y-%m").to_string()
+    }
+}
+
+/// How `cost report` groups the ledger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportGroup {
+    Month,
+    App,
+    Locale,
+}
+
+impl ReportGroup {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "month" => Ok(Self::Month),
+            "app" => Ok(Self::App),
+            "locale" => Ok(Self::Locale),
+            other => anyhow::bail!("Unknown grouping '{}', expected month, app or locale", other),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Month => "Month",
+            Self::App => "App",
+            Self::Locale => "Locale",
+        }
+    }
+}
+
+/// Spend of one group in a report
+#[derive(Debug, Clone, Default)]
+pub struct SpendRow {
+    pub key: String,
+    pub runs: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost: f64,
+}
+
+/// Append-only record of what every AI run cost, shared by every app in the project
+pub struct Ledger {
+    path: PathBuf,
+}
+
+impl Ledger {
+    pub fn open() -> Result<Self> {
+        let mut path = std::env::current_dir()
+            .context("Failed to get current directory")?;
+        path.push(".rosetta");
+        path.push("ledger.jsonl");
+        Ok(Self { path })
+    }
+
+    pub fn append(&self, entry: &LedgerEntry) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let line = serde_json::to_string(entry)
+            .context("Failed to serialize ledger entry")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open cost ledger: {}", self.path.display()))?;
+        writeln!(file, "{}", line)
+            .with_context(|| format!("Failed to write cost ledger: {}", self.path.display()))
+    }
+
+    pub fn entries(&self) -> Result<Vec<LedgerEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read cost ledger: {}", self.path.display()))?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line)
+                    .with_context(|| format!("Failed to parse cost ledger line {}: {}", index + 1, self.path.display()))
+            })
+            .collect()
+    }
+
+    /// Spend of the current calendar month (UTC) across every app
+    pub fn spent_this_month(&self) -> Result<f64> {
+        let now = Utc::now();
+        Ok(self
+            .entries()?
+            .iter()
+            .filter(|entry| entry.recorded_at.year() == now.year() && entry.recorded_at.month() == now.month())
+            .map(|entry| entry.cost)
+            .sum())
+    }
+}
+
+/// Total spend per month, app or locale, sorted by key
+pub fn summarize(entries: &[LedgerEntry], group: ReportGroup) -> Vec<SpendRow> {
+    let mut rows: BTreeMap<String, SpendRow> = BTreeMap::new();
+    let mut add = |key: String, input_tokens: u64, output_tokens: u64, cost: f64| {
+        let row = rows.entry(key.clone()).or_insert_with(|| SpendRow { key, ..Default::default() });
+        row.runs += 1;
+        row.input_tokens += input_tokens;
+        row.output_tokens += output_tokens;
+        row.cost += cost;
+    };
+
+    for entry in entries {
+        match group {
+            ReportGroup::Month => add(entry.month(), entry.input_tokens, entry.output_tokens, entry.cost),
+            ReportGroup::App => add(entry.app_id.clone(), entry.input_tokens, entry.output_tokens, entry.cost),
+            ReportGroup::Locale => {
+                for (locale, spend) in &entry.locales {
+                    add(locale.clone(), spend.input_tokens, spend.output_tokens, spend.cost);
+                }
+            }
+        }
+    }
+
+    rows.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn usage(input: u64, output: u64) -> TokenUsage {
+        TokenUsage { input, output }
+    }
+
+    fn entry(app_id: &str, recorded_at: DateTime<Utc>, spends: &[(&str, &str, u64, u64, f64)]) -> LedgerEntry {
+        let mut spend = RunSpend::default();
+        for (locale, field, input, output, cost) in spends {
+            spend.add(locale, field, usage(*input, *output), *cost);
+        }
+        LedgerEntry { recorded_at, ..LedgerEntry::new(app_id, "openai", "gpt-4o-mini", spend, true) }
+    }
+
+    #[test]
+    fn run_spend_adds_up_per_locale() {
+        let mut spend = RunSpend::default();
+        assert!(spend.is_empty());
+        spend.add("fr-FR", "name", usage(100, 10), 0.25);
+        spend.add("fr-FR", "name", usage(50, 5), 0.5);
+        spend.add("de-DE", "subtitle", usage(10, 1), 0.25);
+
+        assert_eq!(spend.locales["fr-FR"].fields, ["name"]);
+        assert_eq!(spend.cost(), 1.0);
+        assert_eq!((spend.usage().input, spend.usage().output), (160, 16));
+
+        let entry = LedgerEntry::new("com.time.JustTime", "openai", "gpt-4o-mini", spend, true);
+        assert_eq!((entry.input_tokens, entry.output_tokens, entry.cost), (160, 16, 1.0));
+    }
+
+    #[test]
+    fn entries_are_grouped_by_month_app_or_locale() {
+        let march = Utc.with_ymd_and_hms(2025, 3, 31, 23, 59, 0).unwrap();
+        let april = Utc.with_ymd_and_hms(2025, 4, 1, 0, 0, 0).unwrap();
+        let entries = [
+            entry("com.a", march, &[("fr-FR", "name", 100, 10, 0.25), ("ja", "name", 100, 20, 0.5)]),
+            entry("com.b", april, &[("fr-FR", "name", 200, 20, 0.5)]),
+            entry("com.a", april, &[("ja", "keywords", 10, 1, 0.25)]),
+        ];
+        assert_eq!(entries[0].month(), "2025-03");
+
+        let rows = |group| -> Vec<(String, usize, u64, f64)> {
+            summarize(&entries, group).into_iter().map(|row| (row.key, row.runs, row.input_tokens, row.cost)).collect()
+        };
+        assert_eq!(rows(ReportGroup::Month), [("2025-03".to_string(), 1, 200, 0.75), ("2025-04".to_string(), 2, 210, 0.75)]);
+        assert_eq!(rows(ReportGroup::App), [("com.a".to_string(), 2, 210, 1.0), ("com.b".to_string(), 1, 200, 0.5)]);
+        assert_eq!(rows(ReportGroup::Locale), [("fr-FR".to_string(), 2, 300, 0.75), ("ja".to_string(), 2, 110, 0.75)]);
+    }
+
+    #[test]
+    fn report_groups_are_parsed_by_name() {
+        assert_eq!(ReportGroup::parse("locale").unwrap(), ReportGroup::Locale);
+        assert!(ReportGroup::parse("week").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+}
+
+/// One App Store Connect localization
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LocaleInfo {
+    /// Code exactly as App Store Connect returns it (`ja`, `en-AU`, `zh-Hans`)
+    pub code: &'static str,
+    /// English display name, also used in AI prompts
+    pub name: &'static str,
+    pub native_name: &'static str,
+    /// ISO 15924 script code
+    pub script: &'static str,
+    pub direction: TextDirection,
+    pub flag: &'static str,
+    /// Other spellings people use for the same locale (`ja-JP`, `zh-CN`)
+    pub aliases: &'static [&'static str],
+}
+
+const fn locale(
+    code: &'static str,
+    name: &'static str,
+    native_name: &'static str,
+    script: &'static str,
+    flag: &'static str,
+    aliases: &'static [&'static str],
+) -> LocaleInfo {
+    let direction = match script.as_bytes() {
+        b"Arab" | b"Hebr" => TextDirection::Rtl,
+        _ => TextDirection::Ltr,
+    };
+    LocaleInfo { code, name, native_name, script, direction, flag, aliases }
+}
+
+/// Every locale App Store Connect accepts for app and version localizations
+pub const LOCALES: &[LocaleInfo] = &[
+    locale("ar-SA", "Arabic", "العربية", "Arab", "🇸🇦", &["ar"]),
+    locale("bn-BD", "Bangla", "বাংলা", "Beng", "🇧🇩", &["bn"]),
+    locale("ca", "Catalan", "Català", "Latn", "🇪🇸", &["ca-ES"]),
+    locale("zh-Hans", "Chinese (Simplified)", "简体中文", "Hans", "🇨🇳", &["zh", "zh-CN", "zh-SG", "zh-Hans-CN"]),
+    locale("zh-Hant", "Chinese (Traditional)", "繁體中文", "Hant", "🇹🇼", &["zh-TW", "zh-HK", "zh-Hant-TW"]),
+    locale("hr", "Croatian", "Hrvatski", "Latn", "🇭🇷", &["hr-HR"]),
+    locale("cs", "Czech", "Čeština", "Latn", "🇨🇿", &["cs-CZ"]),
+    locale("da", "Danish", "Dansk", "Latn", "🇩🇰", &["da-DK"]),
+    locale("nl-NL", "Dutch", "Nederlands", "Latn", "🇳🇱", &["nl"]),
+    locale("en-AU", "English (Australia)", "English (Australia)", "Latn", "🇦🇺", &[]),
+    locale("en-CA", "English (Canada)", "English (Canada)", "Latn", "🇨🇦", &[]),
+    locale("en-GB", "English (U.K.)", "English (UK)", "Latn", "🇬🇧", &["en-UK"]),
+    locale("en-US", "English (U.S.)", "English (US)", "Latn", "🇺🇸", &["en"]),
+    locale("fi", "Finnish", "Suomi", "Latn", "🇫🇮", &["fi-FI"]),
+    locale("fr-FR", "French", "Français", "Latn", "🇫🇷", &["fr"]),
+    locale("fr-CA", "French (Canada)", "Français (Canada)", "Latn", "🇨🇦", &[]),
+    locale("de-DE", "German", "Deutsch", "Latn", "🇩🇪", &["de"]),
+    locale("el", "Greek", "Ελληνικά", "Grek", "🇬🇷", &["el-GR"]),
+    locale("gu-IN", "Gujarati", "ગુજરાતી", "Gujr", "🇮🇳", &["gu"]),
+    locale("he", "Hebrew", "עברית", "Hebr", "🇮🇱", &["he-IL", "iw"]),
+    locale("hi", "Hindi", "हिन्दी", "Deva", "🇮🇳", &["hi-IN"]),
+    locale("hu", "Hungarian", "Magyar", "Latn", "🇭🇺", &["hu-HU"]),
+    locale("id", "Indonesian", "Bahasa Indonesia", "Latn", "🇮🇩", &["id-ID", "in"]),
+    locale("it", "Italian", "Italiano", "Latn", "🇮🇹", &["it-IT"]),
+    locale("ja", "Japanese", "日本語", "Jpan", "🇯🇵", &["ja-JP"]),
+    locale("kn-IN", "Kannada", "ಕನ್ನಡ", "Knda", "🇮🇳", &["kn"]),
+    locale("ko", "Korean", "한국어", "Kore", "🇰🇷", &["ko-KR"]),
+    locale("ms", "Malay", "Bahasa Melayu", "Latn", "🇲🇾", &["ms-MY"]),
+    locale("ml-IN", "Malayalam", "മലയാളം", "Mlym", "🇮🇳", &["ml"]),
+    locale("mr-IN", "Marathi", "मराठी", "Deva", "🇮🇳", &["mr"]),
+    locale("no", "Norwegian", "Norsk", "Latn", "🇳🇴", &["nb", "nb-NO", "no-NO"]),
+    locale("or-IN", "Odia", "ଓଡ଼ିଆ", "Orya", "🇮🇳", &["or"]),
+    locale("pl", "Polish", "Polski", "Latn", "🇵🇱", &["pl-PL"]),
+    locale("pt-BR", "Portuguese (Brazil)", "Português (Brasil)", "Latn", "🇧🇷", &[]),
+    locale("pt-PT", "Portuguese (Portugal)", "Português (Portugal)", "Latn", "🇵🇹", &["pt"]),
+    locale("pa-IN", "Punjabi", "ਪੰਜਾਬੀ", "Guru", "🇮🇳", &["pa"]),
+    locale("ro", "Romanian", "Română", "Latn", "🇷🇴", &["ro-RO"]),
+    locale("ru", "Russian", "Русский", "Cyrl", "🇷🇺", &["ru-RU"]),
+    locale("sk", "Slovak", "Slovenčina", "Latn", "🇸🇰", &["sk-SK"]),
+    locale("sl-SI", "Slovenian", "Slovenščina", "Latn", "🇸🇮", &["sl"]),
+    locale("es-MX", "Spanish (Mexico)", "Español (México)", "Latn", "🇲🇽", &["es-419"]),
+    locale("es-ES", "Spanish (Spain)", "Español (España)", "Latn", "🇪🇸", &["es"]),
+    locale("sv", "Swedish", "Svenska", "Latn", "🇸🇪", &["sv-SE"]),
+    locale("ta-IN", "Tamil", "தமிழ்", "Taml", "🇮🇳", &["ta"]),
+    locale("te-IN", "Telugu", "తెలుగు", "Telu", "🇮🇳", &["te"]),
+    locale("th", "Thai", "ไทย", "Thai", "🇹🇭", &["th-TH"]),
+    locale("tr", "Turkish", "Türkçe", "Latn", "🇹🇷", &["tr-TR"]),
+    locale("uk", "Ukrainian", "Українська", "Cyrl", "🇺🇦", &["uk-UA"]),
+    locale("ur-PK", "Urdu", "اردو", "Arab", "🇵🇰", &["ur"]),
+    locale("vi", "Vietnamese", "Tiếng Việt", "Latn", "🇻🇳", &["vi-VN"]),
+];
+
+fn normalize(code: &str) -> String {
+    code.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+/// Find a locale by its App Store Connect code
+pub fn get(code: &str) -> Option<&'static LocaleInfo> {
+    let code = normalize(code);
+    LOCALES.iter().find(|l| l.code.to_ascii_lowercase() == code)
+}
+
+/// Find a locale by its code or any alias (`ja-JP` → `ja`)
+pub fn resolve(code: &str) -> Option<&'static LocaleInfo> {
+    get(code).or_else(|| {
+        let code = normalize(code);
+        LOCALES
+            .iter()
+            .find(|l| l.aliases.iter().any(|alias| alias.to_ascii_lowercase() == code))
+    })
+}
+
+/// Emoji flag for display; 🌐 for codes App Store Connect doesn't know
+pub fn flag(code: &str) -> &'static str {
+    resolve(code).map(|l| l.flag).unwrap_or("🌐")
+}
+
+/// How a locale is named in AI prompts, e.g. `Japanese (日本語)`
+pub fn prompt_name(code: &str) -> String {
+    match resolve(code) {
+        Some(l) if l.name == l.native_name => l.name.to_string(),
+        Some(l) => format!("{} ({})", l.name, l.native_name),
+        None => code.to_string(),
+    }
+}
+
+/// Check that `code` is spelled exactly as App Store Connect expects
+pub fn validate_code(code: &str) -> anyhow::Result<()> {
+    match get(code) {
+        Some(l) if l.code == code => Ok(()),
+        Some(l) => anyhow::bail!("Locale '{}' must be written as '{}'", code, l.code),
+        None => match resolve(code) {
+            Some(l) => anyhow::bail!(
+                "'{}' is not an App Store Connect locale, did you mean '{}' ({})?",
+                code, l.code, l.name
+            ),
+            None => anyhow::bail!("'{}' is not an App Store Connect locale", code),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(code: &str) -> String {
+        validate_code(code).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn codes_spelled_as_app_store_connect_does_are_valid() {
+        for code in ["ja", "en-US", "zh-Hans", "pt-BR"] {
+            assert!(validate_code(code).is_ok(), "{} should be valid", code);
+        }
+    }
+
+    #[test]
+    fn case_mismatches_name_the_exact_spelling() {
+        assert_eq!(error("EN-us"), "Locale 'EN-us' must be written as 'en-US'");
+        assert_eq!(error("zh_hans"), "Locale 'zh_hans' must be written as 'zh-Hans'");
+    }
+
+    #[test]
+    fn aliases_suggest_the_app_store_connect_code() {
+        assert_eq!(error("ja-JP"), "'ja-JP' is not an App Store Connect locale, did you mean 'ja' (Japanese)?");
+        assert_eq!(
+            error("zh-CN"),
+            "'zh-CN' is not an App Store Connect locale, did you mean 'zh-Hans' (Chinese (Simplified))?"
+        );
+    }
+
+    #[test]
+    fn unknown_codes_are_rejected_without_a_suggestion() {
+        assert_eq!(error("xx-YY"), "'xx-YY' is not an App Store Connect locale");
+        assert!(resolve("xx-YY").is_none());
+        assert_eq!(flag("xx-YY"), "🌐");
+        assert_eq!(prompt_name("xx-YY"), "xx-YY");
+    }
+
+    #[test]
+    fn resolve_accepts_aliases_in_any_case() {
+        assert_eq!(resolve("JA_jp").map(|l| l.code), Some("ja"));
+        assert_eq!(resolve(" nb-no ").map(|l| l.code), Some("no"));
+        assert_eq!(resolve("fr-FR").map(|l| l.code), Some("fr-FR"));
+        assert_eq!(prompt_name("ja-JP"), "Japanese (日本語)");
+    }
+}
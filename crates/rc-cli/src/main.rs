@@ -2,9 +2,30 @@ use clap::{Parser, Subcommand};
 use anyhow::Result;
 use std::path::PathBuf;
 
+mod changes;
 mod config;
 mod commands;
+mod evaluation;
 mod formatter;
+mod glossary;
+mod history;
+mod ledger;
+mod locales;
+mod memory;
+mod product_page;
+mod provenance;
+mod provider;
+mod style;
+mod templates;
+mod text_metrics;
+mod validation;
+mod workspace;
+
+/// Exit code of a check that found something, like `git diff --exit-code`: differences for `diff`,
+/// errors for `validate`
+pub const EXIT_FOUND: i32 = 1;
+/// Exit code of a command that failed, so scripts can tell it apart from `EXIT_FOUND`
+pub const EXIT_ERROR: i32 = 2;
 
 #[derive(Parser)]
 #[command(name = "rosetta-connect")]
@@ -51,6 +72,9 @@ enum Commands {
         /// Filter specific locales
         #[arg(long, value_delimiter = ',')]
         locales: Vec<String>,
+        /// Overwrite local changes that have not been pushed
+        #[arg(long)]
+        force: bool,
     },
     /// Check version status and workflow readiness
     Status {
@@ -69,35 +93,115 @@ enum Commands {
         /// AI model to use
         #[arg(long)]
         model: Option<String>,
+        /// Replace fields that were edited or reviewed locally
+        #[arg(long)]
+        overwrite: bool,
+        /// Translate the source text on App Store Connect instead of the local workspace
+        #[arg(long)]
+        from_remote: bool,
+        /// Translate every source field, not only new or changed ones
+        #[arg(long)]
+        all: bool,
+        /// Prompt template for every field (see 'template list')
+        #[arg(long)]
+        template: Option<String>,
     },
     /// Show differences between local and remote content
-    Diff,
+    Diff {
+        /// Only compare these locales
+        #[arg(long, value_delimiter = ',')]
+        locales: Vec<String>,
+        /// Only compare these fields (name, subtitle, promotionalText, description, keywords, whatsNew)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+        /// Output format: table, json
+        #[arg(long, default_value = "table")]
+        format: String,
+        /// Compare two downloaded versions instead of local vs remote: old version
+        #[arg(long, requires = "to")]
+        from: Option<String>,
+        /// New version to compare against --from
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+    },
     /// Upload text and screenshots to App Store Connect
     Push {
         /// Version number
-        version: String,
+        #[arg(required_unless_present = "apply")]
+        version: Option<String>,
         /// Skip confirmation prompts
         #[arg(long)]
         yes: bool,
+        /// Compare with App Store Connect and write a plan file instead of uploading
+        #[arg(long, conflicts_with = "apply")]
+        plan: bool,
+        /// Plan file to write (default: rosetta-plan-<version>.json)
+        #[arg(long, requires = "plan")]
+        out: Option<PathBuf>,
+        /// Upload the changes recorded in a plan file
+        #[arg(long, value_name = "PLANFILE")]
+        apply: Option<PathBuf>,
     },
     /// Validate content against App Store guidelines
-    Validate,
+    Validate {
+        /// Only validate these locales
+        #[arg(long, value_delimiter = ',')]
+        locales: Vec<String>,
+        /// Output format: table, json
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
     /// Preview generated content locally
     Preview {
         /// Locale to preview
         #[arg(long)]
         locale: Option<String>,
+        /// Simulate the App Store search result and product page, and write an HTML preview
+        #[arg(long)]
+        render: bool,
+        /// HTML file to write with --render (default: preview-<locale>.html)
+        #[arg(long, requires = "render")]
+        html: Option<PathBuf>,
     },
-    /// Rollback to previous version
+    /// Mark translations as reviewed against the current source text
+    Review {
+        /// Locales to mark (default: every target locale in the workspace)
+        #[arg(long, value_delimiter = ',')]
+        locales: Vec<String>,
+        /// Only mark these fields (name, subtitle, promotionalText, description, keywords, whatsNew)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+    },
+    /// Restore the local workspace from a recorded snapshot
     Rollback {
-        /// Version to rollback to
-        version: String,
+        /// Snapshot ID (or unique prefix) or App Store version to restore
+        target: String,
+        /// Push the restored content to this App Store version afterwards
+        #[arg(long, value_name = "VERSION")]
+        push: Option<String>,
+        /// Skip confirmation prompts
+        #[arg(long)]
+        yes: bool,
+    },
+    /// List snapshots recorded by pull and push
+    History {
+        /// Only show the most recent snapshots
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Output format: table, json
+        #[arg(long, default_value = "table")]
+        format: String,
     },
     /// Estimate AI API call costs
     Cost {
+        #[command(subcommand)]
+        action: Option<CostAction>,
         /// Show detailed breakdown
         #[arg(long)]
         detailed: bool,
+        /// Estimate from the source text on App Store Connect instead of the local workspace
+        #[arg(long)]
+        from_remote: bool,
     },
     /// Manage AI prompt templates
     Template {
@@ -106,29 +210,79 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum CostAction {
+    /// Summarize what past translate and template eval runs spent
+    Report {
+        /// Group by: month, app, locale
+        #[arg(long, default_value = "month")]
+        by: String,
+        /// Only include one month (YYYY-MM)
+        #[arg(long)]
+        month: Option<String>,
+        /// Output format: table, json
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum TemplateAction {
     /// List available templates
     List,
-    /// Create a new template
+    /// Print a template, or with --resolved the full prompt translate would send
+    Show {
+        /// Template to show; with --resolved, overrides the one chosen in [ai.templates]
+        #[arg(required_unless_present = "resolved")]
+        name: Option<String>,
+        /// Render the prompt for one field of one locale from the local source text
+        #[arg(long, requires_all = ["locale", "field"])]
+        resolved: bool,
+        /// Target locale for --resolved
+        #[arg(long, requires = "resolved")]
+        locale: Option<String>,
+        /// Field for --resolved (name, subtitle, promotionalText, description, keywords, whatsNew)
+        #[arg(long, requires = "resolved")]
+        field: Option<String>,
+    },
+    /// Score a template against reference translations and compare it with its previous version
+    Eval {
+        name: String,
+        /// Fixture file (default: .rosetta/eval.json, or the built-in fixtures)
+        #[arg(long)]
+        fixtures: Option<PathBuf>,
+        /// Only evaluate these locales
+        #[arg(long, value_delimiter = ',')]
+        locales: Vec<String>,
+        /// AI model to use
+        #[arg(long)]
+        model: Option<String>,
+        /// Show every output with its scores
+        #[arg(long)]
+        detailed: bool,
+    },
+    /// Create a template from a file
     Create {
         name: String,
         /// Template file path
         #[arg(long)]
         file: PathBuf,
     },
-    /// Edit an existing template
+    /// Edit a template in $EDITOR
     Edit {
         name: String,
     },
     /// Delete a template
     Delete {
         name: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
     },
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     
     let cli = Cli::parse();
     
@@ -136,13 +290,21 @@ async fn main() -> Result<()> {
         std::env::set_var("NODE_DEBUG", "1");
     }
     
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(EXIT_ERROR);
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
     match cli.command {
         Commands::Init { bundle_id, default_locale } => {
             commands::init::run(bundle_id, default_locale, &cli.config).await
         }
-        Commands::Pull { force_refresh, retry_count, format, export, locales } => {
+        Commands::Pull { force_refresh, retry_count, format, export, locales, force } => {
             commands::pull::run(&cli.config, commands::pull::PullOptions {
                 force_refresh,
+                force,
                 retry_count,
                 output_format: format,
                 export_file: export,
@@ -152,26 +314,78 @@ async fn main() -> Result<()> {
         Commands::Status { all_versions, detailed } => {
             commands::status::run(&cli.config, all_versions, detailed).await
         }
-        Commands::Translate { locales, model } => {
-            commands::translate::run(locales, model, &cli.config).await
+        Commands::Translate { locales, model, overwrite, from_remote, all, template } => {
+            commands::translate::run(&cli.config, commands::translate::TranslateOptions {
+                locales,
+                model,
+                overwrite,
+                from_remote,
+                all,
+                template,
+            }).await
+        }
+        Commands::Diff { locales, fields, format, from, to } => {
+            commands::diff::run(&cli.config, commands::diff::DiffOptions {
+                locales,
+                fields,
+                output_format: format,
+                from,
+                to,
+            }).await
+        }
+        Commands::Push { version, yes, plan, out, apply } => {
+            commands::push::run(&cli.config, commands::push::PushOptions {
+                version,
+                yes,
+                plan,
+                plan_file: out,
+                apply,
+            }).await
+        }
+        Commands::Validate { locales, format } => {
+            commands::validate::run(&cli.config, commands::validate::ValidateOptions {
+                locales,
+                output_format: format,
+            }).await
         }
-        Commands::Diff => {
-            commands::diff::run(&cli.config).await
+        Commands::Preview { locale, render, html } => {
+            commands::preview::run(&cli.config, commands::preview::PreviewOptions {
+                locale,
+                render,
+                html_file: html,
+            }).await
         }
-        Commands::Push { version, yes } => {
-            commands::push::run(version, yes, &cli.config).await
+        Commands::Review { locales, fields } => {
+            commands::review::run(&cli.config, commands::review::ReviewOptions {
+                locales,
+                fields,
+            }).await
         }
-        Commands::Validate => {
-            commands::validate::run(&cli.config).await
+        Commands::Rollback { target, push, yes } => {
+            commands::rollback::run(&cli.config, commands::rollback::RollbackOptions {
+                target,
+                push_version: push,
+                yes,
+            }).await
         }
-        Commands::Preview { locale } => {
-            commands::preview::run(locale, &cli.config).await
+        Commands::History { limit, format } => {
+            commands::history::run(&cli.config, commands::history::HistoryOptions {
+                limit,
+                output_format: format,
+            }).await
         }
-        Commands::Rollback { version } => {
-            commands::rollback::run(version, &cli.config).await
+        Commands::Cost { action: Some(CostAction::Report { by, month, format }), .. } => {
+            commands::cost::report(&cli.config, commands::cost::ReportOptions {
+                by,
+                month,
+                output_format: format,
+            }).await
         }
-        Commands::Cost { detailed } => {
-            commands::cost::run(detailed, &cli.config).await
+        Commands::Cost { action: None, detailed, from_remote } => {
+            commands::cost::run(&cli.config, commands::cost::CostOptions {
+                detailed,
+                from_remote,
+            }).await
         }
         Commands::Template { action } => {
             commands::template::run(action, &cli.config).await
@@ -0,0 +1,330 @@
+use crate::config::{Config, MemoryConfig};
+use crate::workspace::LocaleFields;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Where an approved translation pair came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryOrigin {
+    /// A translation marked as reviewed with `review`
+    Reviewed,
+    /// A localization pulled from App Store Connect, i.e. already published
+    Pulled,
+}
+
+/// One source text and its approved translation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryEntry {
+    pub source_locale: String,
+    pub target_locale: String,
+    pub source: String,
+    pub target: String,
+    pub origin: MemoryOrigin,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A stored translation of text equal or similar to the text being translated
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryMatch {
+    pub source: String,
+    pub target: String,
+    /// 1.0 for an exact match
+    pub score: f64,
+}
+
+/// Result of consulting the memory for one field
+#[derive(Debug, Clone)]
+pub enum MemoryLookup {
+    /// The whole text, or every paragraph of it, was translated before
+    Exact(String),
+    /// Similar or partially matching translations, to guide the AI
+    References(Vec<MemoryMatch>),
+    None,
+}
+
+/// Approved translations shared by every app in the project, in `.rosetta/memory.json`.
+/// Entries are keyed by source text and source/target locale; long texts are also stored
+/// paragraph by paragraph so boilerplate is reused across descriptions.
+pub struct TranslationMemory {
+    path: PathBuf,
+    settings: MemoryConfig,
+    entries: Vec<MemoryEntry>,
+}
+
+impl TranslationMemory {
+    pub fn open(config: &Config) -> Result<Self> {
+        let mut path = std::env::current_dir()
+            .context("Failed to get current directory")?;
+        path.push(".rosetta");
+        path.push("memory.json");
+
+        let entries = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read translation memory: {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse translation memory: {}", path.display()))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            path,
+            settings: config.memory.clone().unwrap_or_default(),
+            entries,
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = serde_json::to_string_pretty(&self.entries)
+            .context("Failed to serialize translation memory")?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write translation memory: {}", self.path.display()))
+    }
+
+    /// Store a translation pair, replacing an older translation of the same source text.
+    /// Untranslated text (target equal to source) is not stored. Returns whether anything changed.
+    pub fn add(&mut self, source_locale: &str, target_locale: &str, source: &str, target: &str, origin: MemoryOrigin) -> bool {
+        let mut changed = self.add_segment(source_locale, target_locale, source, target, origin);
+
+        // Paragraphs are only reusable when both sides split the same way
+        let source_paragraphs = paragraphs(source);
+        let target_paragraphs = paragraphs(target);
+        if source_paragraphs.len() > 1 && source_paragraphs.len() == target_paragraphs.len() {
+            for (source, target) in source_paragraphs.iter().zip(&target_paragraphs) {
+                changed |= self.add_segment(source_locale, target_locale, source, target, origin);
+            }
+        }
+        changed
+    }
+
+    /// Store every field of a localization against the same field of the source locale.
+    /// Returns the number of fields that added or updated an entry.
+    pub fn add_fields(
+        &mut self,
+        source_locale: &str,
+        target_locale: &str,
+        source: &LocaleFields,
+        target: &LocaleFields,
+        origin: MemoryOrigin,
+    ) -> usize {
+        target
+            .iter()
+            .filter_map(|(field, text)| source.get(field).map(|source_text| (source_text, text)))
+            .filter(|(source_text, text)| self.add(source_locale, target_locale, source_text, text, origin))
+            .count()
+    }
+
+    /// Look up a text before sending it to the AI
+    pub fn lookup(&self, source_locale: &str, target_locale: &str, text: &str) -> MemoryLookup {
+        if let Some(entry) = self.find_exact(source_locale, target_locale, text) {
+            return MemoryLookup::Exact(entry.target.clone());
+        }
+
+        let text_paragraphs = paragraphs(text);
+        let mut references = Vec::new();
+        if text_paragraphs.len() > 1 {
+            let exact: Vec<Option<&MemoryEntry>> = text_paragraphs
+                .iter()
+                .map(|paragraph| self.find_exact(source_locale, target_locale, paragraph))
+                .collect();
+            if exact.iter().all(|entry| entry.is_some()) {
+                let joined: Vec<&str> = exact.iter().flatten().map(|entry| entry.target.as_str()).collect();
+                return MemoryLookup::Exact(joined.join("\n\n"));
+            }
+
+            for (paragraph, entry) in text_paragraphs.iter().zip(exact) {
+                match entry {
+                    Some(entry) => references.push(MemoryMatch {
+                        source: entry.source.clone(),
+                        target: entry.target.clone(),
+                        score: 1.0,
+                    }),
+                    None => references.extend(self.find_fuzzy(source_locale, target_locale, paragraph)),
+                }
+            }
+        } else {
+            references.extend(self.find_fuzzy(source_locale, target_locale, text));
+        }
+
+        if references.is_empty() {
+            MemoryLookup::None
+        } else {
+            MemoryLookup::References(references)
+        }
+    }
+
+    fn add_segment(&mut self, source_locale: &str, target_locale: &str, source: &str, target: &str, origin: MemoryOrigin) -> bool {
+        let (source, target) = (source.trim(), target.trim());
+        if source.is_empty() || target.is_empty() || normalize(source) == normalize(target) {
+            return false;
+        }
+
+        let key = normalize(source);
+        let existing = self.entries.iter_mut().find(|entry| {
+            entry.source_locale == source_locale && entry.target_locale == target_locale && normalize(&entry.source) == key
+        });
+
+        match existing {
+            Some(entry) if entry.target == target => false,
+            Some(entry) => {
+                entry.target = target.to_string();
+                entry.origin = origin;
+                entry.updated_at = Utc::now();
+                true
+            }
+            None => {
+                self.entries.push(MemoryEntry {
+                    source_locale: source_locale.to_string(),
+                    target_locale: target_locale.to_string(),
+                    source: source.to_string(),
+                    target: target.to_string(),
+                    origin,
+                    updated_at: Utc::now(),
+                });
+                true
+            }
+        }
+    }
+
+    fn find_exact(&self, source_locale: &str, target_locale: &str, text: &str) -> Option<&MemoryEntry> {
+        let key = normalize(text);
+        self.entries.iter().find(|entry| {
+            entry.source_locale == source_locale && entry.target_locale == target_locale && normalize(&entry.source) == key
+        })
+    }
+
+    /// Best entry at least `fuzzy_threshold` similar (normalized Levenshtein over characters)
+    fn find_fuzzy(&self, source_locale: &str, target_locale: &str, text: &str) -> Option<MemoryMatch> {
+        let key = normalize(text);
+        let key_len = key.chars().count() as f64;
+        let threshold = self.settings.fuzzy_threshold;
+
+        self.entries
+            .iter()
+            .filter(|entry| entry.source_locale == source_locale && entry.target_locale == target_locale)
+            .filter_map(|entry| {
+                let candidate = normalize(&entry.source);
+                // Texts whose lengths differ this much can't reach the threshold
+                let len = candidate.chars().count() as f64;
+                if len.min(key_len) / len.max(key_len).max(1.0) < threshold {
+                    return None;
+                }
+                let score = strsim::normalized_levenshtein(&key, &candidate);
+                (score >= threshold).then(|| MemoryMatch {
+                    source: entry.source.clone(),
+                    target: entry.target.clone(),
+                    score,
+                })
+            })
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+    }
+}
+
+/// Whitespace-insensitive form of a text used as the memory key
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Non-empty paragraphs separated by blank lines
+fn paragraphs(text: &str) -> Vec<String> {
+    text.split("\n\n")
+        .map(|paragraph| paragraph.trim().to_string())
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory() -> TranslationMemory {
+        TranslationMemory {
+            path: PathBuf::from("memory.json"),
+            settings: MemoryConfig::default(),
+            entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn exact_matches_ignore_whitespace_and_locale_pairs() {
+        let mut memory = memory();
+        assert!(memory.add("en-US", "fr-FR", "Tell time  at a glance", "L'heure d'un coup d'œil", MemoryOrigin::Reviewed));
+
+        assert!(matches!(
+            memory.lookup("en-US", "fr-FR", " Tell time at\na glance "),
+            MemoryLookup::Exact(target) if target == "L'heure d'un coup d'œil"
+        ));
+        assert!(matches!(memory.lookup("en-US", "de-DE", "Tell time at a glance"), MemoryLookup::None));
+    }
+
+    #[test]
+    fn untranslated_and_repeated_pairs_are_not_stored() {
+        let mut memory = memory();
+        assert!(!memory.add("en-US", "fr-FR", "Hands Time", "Hands Time", MemoryOrigin::Pulled));
+        assert!(memory.add("en-US", "fr-FR", "Clock", "Horloge", MemoryOrigin::Pulled));
+        assert!(!memory.add("en-US", "fr-FR", "Clock", "Horloge", MemoryOrigin::Reviewed));
+        assert!(memory.add("en-US", "fr-FR", "Clock", "Pendule", MemoryOrigin::Reviewed));
+
+        assert_eq!(memory.len(), 1);
+        assert_eq!(memory.entries[0].target, "Pendule");
+        assert_eq!(memory.entries[0].origin, MemoryOrigin::Reviewed);
+    }
+
+    #[test]
+    fn texts_made_of_known_paragraphs_are_exact() {
+        let mut memory = memory();
+        let source = "Widgets for every screen.\n\nPrivacy first.";
+        memory.add("en-US", "ja", source, "あらゆる画面にウィジェット。\n\nプライバシー重視。", MemoryOrigin::Pulled);
+        memory.add("en-US", "ja", "Themes you love.", "お気に入りのテーマ。", MemoryOrigin::Pulled);
+
+        assert!(matches!(
+            memory.lookup("en-US", "ja", "Themes you love.\n\nPrivacy first."),
+            MemoryLookup::Exact(target) if target == "お気に入りのテーマ。\n\nプライバシー重視。"
+        ));
+    }
+
+    #[test]
+    fn similar_texts_are_references() {
+        let mut memory = memory();
+        memory.add("en-US", "fr-FR", "Support for more languages", "Prise en charge de plus de langues", MemoryOrigin::Pulled);
+        memory.add("en-US", "fr-FR", "Privacy first.", "La confidentialité d'abord.", MemoryOrigin::Pulled);
+
+        let MemoryLookup::References(references) = memory.lookup("en-US", "fr-FR", "Support for more languages!") else {
+            panic!("expected a fuzzy reference");
+        };
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].target, "Prise en charge de plus de langues");
+        assert!(references[0].score >= 0.85 && references[0].score < 1.0);
+
+        assert!(matches!(memory.lookup("en-US", "fr-FR", "Dark mode"), MemoryLookup::None));
+    }
+
+    #[test]
+    fn unknown_paragraphs_get_references_from_the_known_ones() {
+        let mut memory = memory();
+        memory.add("en-US", "fr-FR", "Privacy first.", "La confidentialité d'abord.", MemoryOrigin::Pulled);
+
+        let MemoryLookup::References(references) = memory.lookup("en-US", "fr-FR", "Privacy first.\n\nDark mode at night.") else {
+            panic!("expected references");
+        };
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].score, 1.0);
+    }
+}
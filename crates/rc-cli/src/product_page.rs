@@ -0,0 +1,316 @@
+use crate::locales;
+use crate::text_metrics::{truncate_to_width, wrap};
+use crate::workspace::{LocaleFields, ScreenshotSet};
+use base64::Engine;
+use std::fs;
+
+/// Approximate text areas of the App Store on one device, in terminal columns
+/// (a Latin character is one column, CJK and emoji are two) and in points for HTML.
+/// Column counts are calibrated against SF Pro at the App Store's default text sizes.
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceProfile {
+    pub name: &'static str,
+    /// Screen width in points
+    pub points: u32,
+    /// App name line in a search result card
+    pub search_title_columns: usize,
+    /// Subtitle line in a search result card
+    pub search_subtitle_columns: usize,
+    /// App name in the product page header, which wraps onto two lines
+    pub page_title_columns: usize,
+    /// Subtitle line under the product page title
+    pub page_subtitle_columns: usize,
+    /// Body text: promotional text, description and What's New
+    pub body_columns: usize,
+    /// Lines of description and What's New shown before "more"
+    pub fold_lines: usize,
+}
+
+pub const DEVICES: [DeviceProfile; 3] = [
+    DeviceProfile {
+        name: "iPhone 16",
+        points: 393,
+        search_title_columns: 24,
+        search_subtitle_columns: 28,
+        page_title_columns: 19,
+        page_subtitle_columns: 24,
+        body_columns: 45,
+        fold_lines: 3,
+    },
+    DeviceProfile {
+        name: "iPhone 16 Pro Max",
+        points: 440,
+        search_title_columns: 27,
+        search_subtitle_columns: 32,
+        page_title_columns: 22,
+        page_subtitle_columns: 28,
+        body_columns: 51,
+        fold_lines: 3,
+    },
+    DeviceProfile {
+        name: "iPad Pro 13\"",
+        points: 1032,
+        search_title_columns: 40,
+        search_subtitle_columns: 48,
+        page_title_columns: 40,
+        page_subtitle_columns: 48,
+        body_columns: 110,
+        fold_lines: 3,
+    },
+];
+
+/// Text as it fits in a fixed number of lines
+#[derive(Debug, Clone, Default)]
+pub struct Clipped {
+    pub lines: Vec<String>,
+    /// Whether anything was cut off (a trailing "…" or a "more" button)
+    pub truncated: bool,
+}
+
+impl Clipped {
+    /// Wrap `text` into `columns` and keep the first `max_lines`, ending the last one with "…" when cut
+    fn fit(text: &str, columns: usize, max_lines: usize) -> Self {
+        let all = wrap(text.trim(), columns);
+        if all.len() <= max_lines {
+            return Self { lines: all, truncated: false };
+        }
+
+        let mut lines: Vec<String> = all[..max_lines].to_vec();
+        if let Some(last) = lines.last_mut() {
+            // Leave room for the ellipsis on the last visible line
+            let cut = truncate_to_width(last, columns.saturating_sub(1));
+            *last = if cut.ends_with('…') { cut } else { format!("{}…", cut) };
+        }
+        Self { lines, truncated: true }
+    }
+
+    fn line(text: &str, columns: usize) -> Self {
+        let text = text.trim().replace('\n', " ");
+        let fitted = truncate_to_width(&text, columns);
+        Self {
+            truncated: fitted != text,
+            lines: if text.is_empty() { Vec::new() } else { vec![fitted] },
+        }
+    }
+}
+
+/// A locale's listing laid out on one device
+#[derive(Debug, Clone)]
+pub struct PageRender {
+    pub device: DeviceProfile,
+    pub search_title: Clipped,
+    pub search_subtitle: Clipped,
+    pub page_title: Clipped,
+    pub page_subtitle: Clipped,
+    pub promotional_text: Clipped,
+    pub description: Clipped,
+    pub whats_new: Clipped,
+}
+
+impl PageRender {
+    pub fn new(fields: &LocaleFields, device: DeviceProfile) -> Self {
+        let field = |name: &str| fields.get(name).map(|v| v.as_str()).unwrap_or("");
+
+        Self {
+            device,
+            search_title: Clipped::line(field("name"), device.search_title_columns),
+            search_subtitle: Clipped::line(field("subtitle"), device.search_subtitle_columns),
+            page_title: Clipped::fit(field("name"), device.page_title_columns, 2),
+            page_subtitle: Clipped::line(field("subtitle"), device.page_subtitle_columns),
+            // Promotional text is shown in full above the description
+            promotional_text: Clipped::fit(field("promotionalText"), device.body_columns, usize::MAX),
+            description: Clipped::fit(field("description"), device.body_columns, device.fold_lines),
+            whats_new: Clipped::fit(field("whatsNew"), device.body_columns, device.fold_lines),
+        }
+    }
+
+    /// Problems a reviewer should look at; a folded description is normal and not reported
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.search_title.truncated {
+            warnings.push(format!("App name is cut off in search results: \"{}\"", self.search_title.lines.join("")));
+        }
+        if self.search_subtitle.truncated {
+            warnings.push(format!("Subtitle is cut off in search results: \"{}\"", self.search_subtitle.lines.join("")));
+        }
+        if self.page_title.truncated {
+            warnings.push("App name does not fit in two lines on the product page".to_string());
+        }
+        if self.page_subtitle.truncated {
+            warnings.push("Subtitle is cut off on the product page".to_string());
+        }
+        warnings
+    }
+}
+
+/// Lay out a listing on every device profile
+pub fn render_all(fields: &LocaleFields) -> Vec<PageRender> {
+    DEVICES.iter().map(|device| PageRender::new(fields, *device)).collect()
+}
+
+/// A self-contained HTML page (inline CSS, screenshots embedded once as data URIs) simulating the
+/// search result card and product page of one locale on every device profile
+pub fn to_html(
+    locale: &str,
+    version: Option<&str>,
+    fields: &LocaleFields,
+    screenshots: &[ScreenshotSet],
+    renders: &[PageRender],
+) -> String {
+    let field = |name: &str| escape_html(fields.get(name).map(|v| v.trim()).unwrap_or(""));
+    let direction = locales::resolve(locale).map(|l| l.direction).unwrap_or(locales::TextDirection::Ltr);
+    let dir = match direction {
+        locales::TextDirection::Ltr => "ltr",
+        locales::TextDirection::Rtl => "rtl",
+    };
+
+    // The first display type's first three screenshots, as shown above the fold. Each is embedded
+    // once as a CSS class and referenced from every device section.
+    let mut shot_styles = String::new();
+    let mut shots = String::new();
+    if let Some(set) = screenshots.first() {
+        let files = set.items.iter().take(3).filter_map(|item| fs::read(&item.path).ok().map(|bytes| (item, bytes)));
+        for (index, (item, bytes)) in files.enumerate() {
+            let mime = match item.path.extension().and_then(|e| e.to_str()) {
+                Some("jpg") | Some("jpeg") => "image/jpeg",
+                _ => "image/png",
+            };
+            // Portrait iPhone proportions when the size could not be read
+            let (width, height) = item.dimensions.unwrap_or((1290, 2796));
+            shot_styles.push_str(&format!(
+                ".shot-{} {{ aspect-ratio: {} / {}; background-image: url(\"data:{};base64,{}\"); }}\n",
+                index,
+                width,
+                height,
+                mime,
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            ));
+            shots.push_str(&format!("<div class=\"shot shot-{}\"></div>", index));
+        }
+    }
+
+    let mut devices = String::new();
+    for render in renders {
+        let device = &render.device;
+        let warnings: String = render
+            .warnings()
+            .iter()
+            .map(|w| format!("<li>{}</li>", escape_html(w)))
+            .collect();
+
+        devices.push_str(&format!(
+            r#"<section class="device">
+<h2>{name} <small>{points}pt</small></h2>
+<div class="screen" style="width:{points}px">
+  <div class="card">
+    <div class="icon"></div>
+    <div class="card-text"><div class="title one-line">{name_text}</div><div class="subtitle one-line">{subtitle}</div></div>
+    <div class="get">GET</div>
+  </div>
+  <div class="header">
+    <div class="icon large"></div>
+    <div><div class="page-title">{name_text}</div><div class="subtitle one-line">{subtitle}</div></div>
+  </div>
+  <div class="shots">{images}</div>
+  <p class="promo">{promo}</p>
+  <div class="folded"><p class="clamp">{description}</p><span class="more">more</span></div>
+  <h3>What's New</h3>
+  <div class="folded"><p class="clamp">{whats_new}</p><span class="more">more</span></div>
+</div>
+<ul class="warnings">{warnings}</ul>
+</section>
+"#,
+            name = escape_html(device.name),
+            points = device.points,
+            name_text = field("name"),
+            subtitle = field("subtitle"),
+            images = shots,
+            promo = field("promotionalText"),
+            description = field("description"),
+            whats_new = field("whatsNew"),
+            warnings = warnings,
+        ));
+    }
+
+    let title = format!(
+        "{} {} — {}",
+        locales::flag(locale),
+        escape_html(locale),
+        escape_html(version.unwrap_or("local workspace"))
+    );
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{lang}" dir="{dir}">
+<head>
+<meta charset="utf-8">
+<title>App Store preview: {title}</title>
+<style>
+body {{ font-family: -apple-system, "SF Pro Text", "Helvetica Neue", "PingFang SC", "Hiragino Sans", sans-serif; background: #f2f2f7; color: #1c1c1e; margin: 24px; }}
+.device {{ margin-bottom: 48px; }}
+.device h2 small {{ color: #8e8e93; font-weight: normal; }}
+.screen {{ background: #fff; border-radius: 24px; padding: 16px 20px; box-shadow: 0 2px 12px rgba(0,0,0,.12); box-sizing: border-box; }}
+.card {{ display: flex; align-items: center; gap: 12px; padding-bottom: 16px; border-bottom: 1px solid #e5e5ea; margin-bottom: 16px; }}
+.card-text {{ flex: 1; min-width: 0; }}
+.icon {{ width: 64px; height: 64px; border-radius: 14px; background: linear-gradient(135deg, #5ac8fa, #007aff); flex: none; }}
+.icon.large {{ width: 118px; height: 118px; border-radius: 26px; }}
+.title {{ font-size: 17px; font-weight: 600; }}
+.subtitle {{ font-size: 15px; color: #8e8e93; }}
+.one-line {{ white-space: nowrap; overflow: hidden; text-overflow: ellipsis; }}
+.get {{ background: #f2f2f7; color: #007aff; font-weight: 700; border-radius: 14px; padding: 4px 16px; }}
+.header {{ display: flex; gap: 16px; margin-bottom: 16px; }}
+.header > div:last-child {{ min-width: 0; }}
+.page-title {{ font-size: 24px; font-weight: 700; display: -webkit-box; -webkit-line-clamp: 2; -webkit-box-orient: vertical; overflow: hidden; }}
+.shots {{ display: flex; gap: 8px; overflow: hidden; margin-bottom: 16px; }}
+.shot {{ height: 360px; flex: none; border-radius: 12px; background-size: cover; }}
+{shot_styles}.promo, .clamp {{ font-size: 15px; line-height: 1.35; white-space: pre-line; }}
+.folded {{ position: relative; }}
+.clamp {{ display: -webkit-box; -webkit-line-clamp: 3; -webkit-box-orient: vertical; overflow: hidden; margin: 0; }}
+.more {{ position: absolute; right: 0; bottom: 0; background: #fff; color: #007aff; padding-left: 8px; font-size: 15px; line-height: 1.35; }}
+h3 {{ font-size: 20px; margin: 20px 0 8px; }}
+.warnings {{ color: #c93400; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{devices}</body>
+</html>
+"#,
+        lang = escape_html(locale),
+        dir = dir,
+        title = title,
+        shot_styles = shot_styles,
+        devices = devices,
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::ScreenshotFile;
+
+    #[test]
+    fn screenshots_are_embedded_once_for_all_devices() {
+        let path = std::env::temp_dir().join(format!("rosetta-product-page-{}.png", std::process::id()));
+        fs::write(&path, b"not really a png").unwrap();
+        let screenshots = vec![ScreenshotSet {
+            display_type: "APP_IPHONE_67".to_string(),
+            items: vec![ScreenshotFile { path: path.clone(), dimensions: Some((1290, 2796)) }],
+        }];
+        let fields = LocaleFields::from([("name".to_string(), "Hands Time".to_string())]);
+
+        let html = to_html("en-US", None, &fields, &screenshots, &render_all(&fields));
+        fs::remove_file(&path).unwrap();
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode(b"not really a png");
+        assert_eq!(html.matches(&encoded).count(), 1);
+        assert_eq!(html.matches("class=\"shot shot-0\"").count(), DEVICES.len());
+    }
+}
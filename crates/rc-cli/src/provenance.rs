@@ -0,0 +1,367 @@
+use crate::workspace::{content_hash, LocaleFields, Workspace};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Review state of a machine-translated field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    /// Written by `translate` and not looked at yet
+    Draft,
+    /// Approved by a person; never replaced without `--overwrite`
+    Reviewed,
+}
+
+/// Where the text of one translated field came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldProvenance {
+    pub status: ReviewStatus,
+    /// AI model that wrote the text; `None` for text a person wrote and marked as reviewed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// When `translate` wrote the text, or when human-written text was first marked as reviewed
+    pub translated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub source_locale: String,
+    /// Hash of the source text the translation was made from
+    pub source_hash: String,
+    /// Hash of the text as it was written, to tell an untouched draft from a local edit
+    pub text_hash: String,
+}
+
+/// Provenance of a locale's translated fields (`current/<locale>/provenance.json`), keyed by field
+pub type LocaleProvenance = BTreeMap<String, FieldProvenance>;
+
+/// What happened to one field when translations were saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftOutcome {
+    /// Written as a new or refreshed draft
+    Saved,
+    /// The translation is identical to what is already there
+    Unchanged,
+    /// Kept because someone edited or reviewed it; needs `--overwrite`
+    KeptEdited,
+}
+
+/// Whether a field holds text a person wrote or approved, as opposed to an untouched draft.
+/// Pulled text is recorded as reviewed, and text without provenance (typed in) counts as human-written too.
+pub fn is_human_edited(current: Option<&str>, record: Option<&FieldProvenance>) -> bool {
+    let Some(current) = current.filter(|text| !text.trim().is_empty()) else {
+        return false;
+    };
+
+    match record {
+        Some(record) => record.status == ReviewStatus::Reviewed || record.text_hash != content_hash(current),
+        None => true,
+    }
+}
+
+/// Merge translated fields into a locale as drafts, recording provenance for every field written.
+/// Fields a person edited or reviewed are left alone unless `overwrite` is set.
+pub fn apply_drafts(
+    fields: &mut LocaleFields,
+    provenance: &mut LocaleProvenance,
+    translated: &LocaleFields,
+    source: &LocaleFields,
+    source_locale: &str,
+    model: &str,
+    overwrite: bool,
+) -> BTreeMap<String, DraftOutcome> {
+    let translated_at = Utc::now();
+    let mut outcomes = BTreeMap::new();
+
+    for (field, text) in translated {
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let current = fields.get(field).map(|v| v.as_str());
+        let outcome = if current == Some(text.as_str()) {
+            DraftOutcome::Unchanged
+        } else if !overwrite && is_human_edited(current, provenance.get(field)) {
+            DraftOutcome::KeptEdited
+        } else {
+            fields.insert(field.clone(), text.clone());
+            provenance.insert(field.clone(), FieldProvenance {
+                status: ReviewStatus::Draft,
+                model: Some(model.to_string()),
+                translated_at,
+                reviewed_at: None,
+                source_locale: source_locale.to_string(),
+                source_hash: content_hash(source.get(field).map(|v| v.as_str()).unwrap_or("")),
+                text_hash: content_hash(text),
+            });
+            DraftOutcome::Saved
+        };
+        outcomes.insert(field.clone(), outcome);
+    }
+
+    outcomes
+}
+
+/// Source fields a target locale needs translated: missing or empty in the target, or
+/// translated from source text that has changed since. Text without provenance is left alone.
+pub fn outdated_fields(source: &LocaleFields, target: &LocaleFields, provenance: &LocaleProvenance) -> Vec<String> {
+    source
+        .iter()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .filter(|(field, text)| {
+            let missing = target.get(*field).is_none_or(|current| current.trim().is_empty());
+            let source_changed = provenance
+                .get(*field)
+                .is_some_and(|record| record.source_hash != content_hash(text));
+            missing || source_changed
+        })
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+/// Fields whose translation was made (or reviewed) against source text that has changed since
+pub fn stale_fields(source: &LocaleFields, provenance: &LocaleProvenance) -> Vec<String> {
+    provenance
+        .iter()
+        .filter(|(field, record)| record.source_hash != content_hash(source.get(*field).map(|v| v.as_str()).unwrap_or("")))
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+/// Non-empty fields with no provenance at all, typed in by hand, whose staleness can't be told
+pub fn untracked_fields(fields: &LocaleFields, provenance: &LocaleProvenance) -> Vec<String> {
+    fields
+        .iter()
+        .filter(|(field, text)| !text.trim().is_empty() && !provenance.contains_key(*field))
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+/// Record text pulled from App Store Connect as reviewed against the pulled source text, so a
+/// later source change shows up as stale. Records of drafts that came back unchanged are kept;
+/// records of fields that are now empty are dropped.
+pub fn record_baseline(
+    fields: &LocaleFields,
+    provenance: &mut LocaleProvenance,
+    source: &LocaleFields,
+    source_locale: &str,
+) {
+    let pulled_at = Utc::now();
+    provenance.retain(|field, _| fields.get(field).is_some_and(|text| !text.trim().is_empty()));
+
+    for (field, text) in fields {
+        if text.trim().is_empty() || provenance.get(field).is_some_and(|record| record.text_hash == content_hash(text)) {
+            continue;
+        }
+        provenance.insert(field.clone(), FieldProvenance {
+            status: ReviewStatus::Reviewed,
+            model: None,
+            translated_at: pulled_at,
+            reviewed_at: None,
+            source_locale: source_locale.to_string(),
+            source_hash: content_hash(source.get(field).map(|v| v.as_str()).unwrap_or("")),
+            text_hash: content_hash(text),
+        });
+    }
+}
+
+/// Stale fields of every locale in the working copy except the source locale; locales without
+/// stale fields are left out
+pub fn stale_translations(
+    workspace: &Workspace,
+    source_locale: &str,
+    source: &LocaleFields,
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut stale = BTreeMap::new();
+    for locale in workspace.locales()? {
+        if locale == source_locale {
+            continue;
+        }
+        let fields = stale_fields(source, &workspace.read_provenance(&locale)?);
+        if !fields.is_empty() {
+            stale.insert(locale, fields);
+        }
+    }
+    Ok(stale)
+}
+
+/// Untracked fields of every locale in the working copy except the source locale; locales
+/// without untracked fields are left out
+pub fn untracked_translations(workspace: &Workspace, source_locale: &str) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut untracked = BTreeMap::new();
+    for locale in workspace.locales()? {
+        if locale == source_locale {
+            continue;
+        }
+        let fields = untracked_fields(&workspace.read_locale(&locale)?, &workspace.read_provenance(&locale)?);
+        if !fields.is_empty() {
+            untracked.insert(locale, fields);
+        }
+    }
+    Ok(untracked)
+}
+
+/// Approve the current text of `which` fields (every non-empty field when empty) against the
+/// current source text, which also clears their stale state. Returns the fields marked.
+pub fn mark_reviewed(
+    fields: &LocaleFields,
+    provenance: &mut LocaleProvenance,
+    source: &LocaleFields,
+    source_locale: &str,
+    which: &[String],
+) -> Vec<String> {
+    let reviewed_at = Utc::now();
+    let mut marked = Vec::new();
+
+    for (field, text) in fields {
+        if text.trim().is_empty() || (!which.is_empty() && !which.contains(field)) {
+            continue;
+        }
+
+        let source_hash = content_hash(source.get(field).map(|v| v.as_str()).unwrap_or(""));
+        let record = provenance.entry(field.clone()).or_insert_with(|| FieldProvenance {
+            status: ReviewStatus::Reviewed,
+            model: None,
+            translated_at: reviewed_at,
+            reviewed_at: None,
+            source_locale: source_locale.to_string(),
+            source_hash: source_hash.clone(),
+            text_hash: content_hash(text),
+        });
+        record.status = ReviewStatus::Reviewed;
+        record.reviewed_at = Some(reviewed_at);
+        record.source_locale = source_locale.to_string();
+        record.source_hash = source_hash;
+        record.text_hash = content_hash(text);
+        marked.push(field.clone());
+    }
+
+    marked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> LocaleFields {
+        pairs.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn untouched_drafts_are_not_human_edited() {
+        let source = fields(&[("name", "Hands")]);
+        let mut target = LocaleFields::new();
+        let mut provenance = LocaleProvenance::new();
+        apply_drafts(&mut target, &mut provenance, &fields(&[("name", "Mains")]), &source, "en-US", "gpt-4o", false);
+
+        assert!(!is_human_edited(Some("Mains"), provenance.get("name")));
+        assert!(is_human_edited(Some("Les mains"), provenance.get("name")));
+        assert!(is_human_edited(Some("Mains"), None));
+        assert!(!is_human_edited(Some("  "), None));
+        assert!(!is_human_edited(None, None));
+
+        mark_reviewed(&target, &mut provenance, &source, "en-US", &[]);
+        assert!(is_human_edited(Some("Mains"), provenance.get("name")));
+    }
+
+    #[test]
+    fn drafts_keep_edited_fields_unless_overwriting() {
+        let source = fields(&[("name", "Hands"), ("subtitle", "Clock"), ("keywords", "time")]);
+        let mut target = fields(&[("name", "Mains"), ("subtitle", "Horloge")]);
+        let mut provenance = LocaleProvenance::new();
+        let translated = fields(&[("name", "Mains"), ("subtitle", "Pendule"), ("keywords", "heure"), ("whatsNew", " ")]);
+
+        let outcomes = apply_drafts(&mut target, &mut provenance, &translated, &source, "en-US", "gpt-4o", false);
+        assert_eq!(outcomes["name"], DraftOutcome::Unchanged);
+        assert_eq!(outcomes["subtitle"], DraftOutcome::KeptEdited);
+        assert_eq!(outcomes["keywords"], DraftOutcome::Saved);
+        assert!(!outcomes.contains_key("whatsNew"));
+        assert_eq!(target["subtitle"], "Horloge");
+        assert_eq!(provenance.keys().collect::<Vec<_>>(), ["keywords"]);
+
+        let outcomes = apply_drafts(&mut target, &mut provenance, &translated, &source, "en-US", "gpt-4o", true);
+        assert_eq!(outcomes["subtitle"], DraftOutcome::Saved);
+        assert_eq!(target["subtitle"], "Pendule");
+        assert_eq!(provenance["subtitle"].status, ReviewStatus::Draft);
+    }
+
+    #[test]
+    fn outdated_fields_are_missing_or_translated_from_older_source() {
+        let old_source = fields(&[("name", "Hands"), ("subtitle", "Clock")]);
+        let mut target = LocaleFields::new();
+        let mut provenance = LocaleProvenance::new();
+        let translated = fields(&[("name", "Mains"), ("subtitle", "Horloge")]);
+        apply_drafts(&mut target, &mut provenance, &translated, &old_source, "en-US", "gpt-4o", false);
+        target.insert("keywords".to_string(), "heure".to_string());
+
+        let source = fields(&[
+            ("name", "Hands"),
+            ("subtitle", "Widget clock"),
+            ("keywords", "clock"),
+            ("whatsNew", "Fixes"),
+            ("description", ""),
+        ]);
+        assert_eq!(outdated_fields(&source, &target, &provenance), ["subtitle", "whatsNew"]);
+        assert_eq!(stale_fields(&source, &provenance), ["subtitle"]);
+    }
+
+    #[test]
+    fn reviewing_clears_stale_fields() {
+        let old_source = fields(&[("name", "Hands"), ("subtitle", "Clock")]);
+        let mut target = LocaleFields::new();
+        let mut provenance = LocaleProvenance::new();
+        let translated = fields(&[("name", "Mains"), ("subtitle", "Horloge")]);
+        apply_drafts(&mut target, &mut provenance, &translated, &old_source, "en-US", "gpt-4o", false);
+
+        let source = fields(&[("name", "Hand"), ("subtitle", "Widget clock")]);
+        assert_eq!(stale_fields(&source, &provenance), ["name", "subtitle"]);
+
+        let marked = mark_reviewed(&target, &mut provenance, &source, "en-US", &["subtitle".to_string()]);
+        assert_eq!(marked, ["subtitle"]);
+        assert_eq!(stale_fields(&source, &provenance), ["name"]);
+        assert_eq!(provenance["subtitle"].status, ReviewStatus::Reviewed);
+        assert_eq!(provenance["subtitle"].model.as_deref(), Some("gpt-4o"));
+        assert!(provenance["subtitle"].reviewed_at.is_some());
+    }
+
+    #[test]
+    fn pulled_text_is_stale_once_the_source_changes() {
+        let source = fields(&[("name", "Hands Time"), ("subtitle", "A clock")]);
+        let pulled = fields(&[("name", "Heure"), ("subtitle", "Une horloge")]);
+        let mut provenance = LocaleProvenance::new();
+
+        record_baseline(&pulled, &mut provenance, &source, "en-US");
+        assert!(stale_fields(&source, &provenance).is_empty());
+        assert!(untracked_fields(&pulled, &provenance).is_empty());
+        assert!(provenance.values().all(|record| record.status == ReviewStatus::Reviewed && record.model.is_none()));
+
+        let edited = fields(&[("name", "Hands Time"), ("subtitle", "A widget clock")]);
+        assert_eq!(stale_fields(&edited, &provenance), ["subtitle"]);
+    }
+
+    #[test]
+    fn baseline_keeps_drafts_that_came_back_and_drops_emptied_fields() {
+        let old_source = fields(&[("name", "Hands"), ("subtitle", "Clock")]);
+        let mut provenance = LocaleProvenance::new();
+        let mut target = LocaleFields::new();
+        let translated = fields(&[("name", "Mains"), ("subtitle", "Horloge")]);
+        apply_drafts(&mut target, &mut provenance, &translated, &old_source, "en-US", "gpt-4o", false);
+
+        let new_source = fields(&[("name", "Hands"), ("subtitle", "Widgets")]);
+        record_baseline(&fields(&[("name", "Mains"), ("subtitle", "")]), &mut provenance, &new_source, "en-US");
+
+        assert_eq!(provenance.keys().collect::<Vec<_>>(), ["name"]);
+        assert_eq!(provenance["name"].status, ReviewStatus::Draft);
+        assert_eq!(provenance["name"].model.as_deref(), Some("gpt-4o"));
+    }
+
+    #[test]
+    fn fields_without_provenance_are_untracked() {
+        let target = fields(&[("name", "Heure"), ("subtitle", " "), ("keywords", "horloge")]);
+        let mut provenance = LocaleProvenance::new();
+        mark_reviewed(&target, &mut provenance, &fields(&[("name", "Hands")]), "en-US", &["name".to_string()]);
+
+        assert_eq!(untracked_fields(&target, &provenance), ["keywords"]);
+    }
+}
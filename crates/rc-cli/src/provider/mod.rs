@@ -0,0 +1,133 @@
+mod openai;
+mod prompt;
+pub mod pricing;
+pub mod tokens;
+
+use crate::config::AiConfig;
+use crate::memory::MemoryMatch;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+
+pub use openai::OpenAiCompatible;
+pub use prompt::build_prompt;
+
+/// One field of one locale to translate, with everything the prompt needs
+#[derive(Debug, Clone, Default)]
+pub struct TranslationRequest {
+    pub field: String,
+    pub text: String,
+    pub source_locale: String,
+    pub target_locale: String,
+    /// Language names for the prompt, e.g. "Japanese (日本語)"
+    pub source_language: String,
+    pub target_language: String,
+    pub context: Option<String>,
+    /// Approved translations of similar text from the translation memory
+    pub references: Vec<MemoryMatch>,
+    /// Glossary terms to keep exactly as written
+    pub do_not_translate: Vec<String>,
+    /// Fixed renderings of glossary terms in the target locale
+    pub terms: BTreeMap<String, String>,
+    /// Style guide instructions for the target locale
+    pub style: Vec<String>,
+    /// Set when asking again for a translation that came back over its character limit
+    pub shorten: Option<Shortening>,
+    /// Body of the prompt template; `None` uses the built-in default template
+    pub template: Option<String>,
+}
+
+/// A previous translation that was too long, and the limit it has to fit
+#[derive(Debug, Clone)]
+pub struct Shortening {
+    pub max_length: usize,
+    pub previous: String,
+}
+
+/// Tokens billed for one or more requests
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input += other.input;
+        self.output += other.output;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Translation {
+    pub text: String,
+    pub usage: TokenUsage,
+}
+
+/// A service that translates App Store metadata, selected with `[ai].provider`
+#[async_trait]
+pub trait TranslationProvider: Send + Sync {
+    /// Model name, recorded in the provenance of every draft
+    fn model(&self) -> &str;
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<Translation>;
+}
+
+/// Built-in providers; every one of them speaks the OpenAI chat completions API
+pub const PROVIDERS: [&str; 3] = ["openai", "ollama", "openai-compatible"];
+
+/// Create the provider configured in `[ai]`; `model` overrides `[ai].model`
+pub fn from_config(ai: &AiConfig, model: Option<&str>) -> Result<Box<dyn TranslationProvider>> {
+    let model = model.unwrap_or(&ai.model).to_string();
+
+    let (default_url, default_key_env) = match ai.provider.as_str() {
+        "openai" => (Some("https://api.openai.com/v1"), Some("OPENAI_API_KEY")),
+        "ollama" => (Some("http://localhost:11434/v1"), None),
+        "openai-compatible" => (None, None),
+        other => anyhow::bail!(
+            "Unknown AI provider '{}', expected one of: {}",
+            other,
+            PROVIDERS.join(", ")
+        ),
+    };
+
+    let base_url = ai
+        .base_url
+        .clone()
+        .or(default_url.map(str::to_string))
+        .with_context(|| format!("[ai].base_url is required for provider '{}'", ai.provider))?;
+
+    let api_key = match ai.api_key_env.as_deref().or(default_key_env) {
+        Some(name) => Some(env_value(name).with_context(|| {
+            format!("{} is not set; add it to the environment or to .env", name)
+        })?),
+        None => None,
+    };
+
+    Ok(Box::new(OpenAiCompatible::new(
+        base_url,
+        api_key,
+        model,
+        ai.temperature,
+        ai.max_tokens,
+    )))
+}
+
+/// A variable from the environment, or from the `.env` file `init` creates next to the config
+fn env_value(name: &str) -> Option<String> {
+    if let Ok(value) = std::env::var(name) {
+        return Some(value).filter(|v| !v.is_empty());
+    }
+
+    let cwd = std::env::current_dir().ok()?;
+    [cwd.join(".env"), cwd.join("..").join(".env")]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .find_map(|content| {
+            content.lines().find_map(|line| {
+                let (key, value) = line.trim().split_once('=')?;
+                (key.trim() == name).then(|| value.trim().trim_matches(['"', '\'']).to_string())
+            })
+        })
+        .filter(|v| !v.is_empty())
+}
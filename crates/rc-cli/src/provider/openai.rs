@@ -0,0 +1,109 @@
+use super::{build_prompt, TokenUsage, Translation, TranslationProvider, TranslationRequest};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Attempts per request when the endpoint is rate limited or temporarily unavailable
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Any server implementing `POST {base_url}/chat/completions`: OpenAI itself, Ollama, vLLM,
+/// LM Studio, OpenRouter and other self-hosted or proxy endpoints
+pub struct OpenAiCompatible {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+impl OpenAiCompatible {
+    pub fn new(base_url: String, api_key: Option<String>, model: String, temperature: f32, max_tokens: u32) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model,
+            temperature,
+            max_tokens,
+        }
+    }
+
+    async fn post(&self, body: &Value) -> Result<Value> {
+        let url = format!("{}/chat/completions", self.base_url);
+        let mut attempt = 1;
+        loop {
+            let mut request = self.client.post(&url).json(body).timeout(Duration::from_secs(120));
+            if let Some(key) = &self.api_key {
+                request = request.bearer_auth(key);
+            }
+
+            let response = request.send().await
+                .with_context(|| format!("Failed to reach {}", url))?;
+            let status = response.status();
+            if status.is_success() {
+                return response.json().await
+                    .with_context(|| format!("Invalid response from {}", url));
+            }
+
+            let retryable = status.as_u16() == 429 || status.is_server_error();
+            if retryable && attempt < MAX_ATTEMPTS {
+                tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
+                attempt += 1;
+                continue;
+            }
+
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|v| v.pointer("/error/message").and_then(|m| m.as_str()).map(str::to_string))
+                .unwrap_or(body);
+            anyhow::bail!("{} returned {}: {}", url, status, message.trim());
+        }
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for OpenAiCompatible {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn translate(&self, request: &TranslationRequest) -> Result<Translation> {
+        let body = json!({
+            "model": self.model,
+            "messages": [{ "role": "user", "content": build_prompt(request) }],
+            // f32 → f64 through its shortest decimal form, so 0.3 is sent as 0.3 rather than 0.30000001
+            "temperature": self.temperature.to_string().parse::<f64>().unwrap_or(0.3),
+            "max_tokens": self.max_tokens,
+        });
+
+        let response = self.post(&body).await?;
+        let text = response
+            .pointer("/choices/0/message/content")
+            .and_then(|c| c.as_str())
+            .map(|text| unquote(text.trim(), &request.text))
+            .filter(|text| !text.is_empty())
+            .with_context(|| format!("No translation received from {}", self.model))?;
+
+        let tokens = |name: &str| response.pointer(&format!("/usage/{}", name)).and_then(|t| t.as_u64()).unwrap_or(0);
+        Ok(Translation {
+            text,
+            usage: TokenUsage {
+                input: tokens("prompt_tokens"),
+                output: tokens("completion_tokens"),
+            },
+        })
+    }
+}
+
+/// The prompt quotes the source text, and models often quote their answer the same way
+fn unquote(text: &str, source: &str) -> String {
+    let quoted = |t: &str| t.len() >= 2 && t.starts_with('"') && t.ends_with('"');
+    if quoted(text) && !quoted(source.trim()) {
+        text[1..text.len() - 1].trim().to_string()
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,88 @@
+use super::TokenUsage;
+use crate::config::{AiConfig, ModelPrice};
+use std::collections::BTreeMap;
+
+/// USD per million tokens of the models people commonly translate with, as published by
+/// OpenAI. `[ai.pricing]` overrides and extends this table.
+const MODEL_PRICES: [(&str, f64, f64); 6] = [
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-3.5-turbo", 0.50, 1.50),
+];
+
+/// Built-in prices merged with the ones configured in `[ai.pricing]`
+#[derive(Debug, Clone, Default)]
+pub struct PriceTable {
+    overrides: BTreeMap<String, ModelPrice>,
+}
+
+impl PriceTable {
+    pub fn from_config(ai: &AiConfig) -> Self {
+        Self {
+            overrides: ai.pricing.clone(),
+        }
+    }
+
+    /// Price of a model; dated snapshots ("gpt-4o-2024-08-06") use the price of their model
+    pub fn price(&self, model: &str) -> Option<ModelPrice> {
+        if let Some(price) = self.overrides.get(model) {
+            return Some(*price);
+        }
+        MODEL_PRICES
+            .iter()
+            .filter(|(name, _, _)| model == *name || model.strip_prefix(name).is_some_and(|rest| rest.starts_with("-20")))
+            .map(|(_, input, output)| ModelPrice { input: *input, output: *output })
+            .next()
+    }
+
+    /// Cost in USD, or `None` when the model has no known price
+    pub fn cost(&self, model: &str, usage: TokenUsage) -> Option<f64> {
+        self.price(model)
+            .map(|price| (usage.input as f64 * price.input + usage.output as f64 * price.output) / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dated_snapshots_use_the_price_of_their_model() {
+        let prices = PriceTable::default();
+        let price = prices.price("gpt-4o-2024-08-06").unwrap();
+        assert_eq!((price.input, price.output), (2.50, 10.00));
+
+        // A longer model name is a different model, not a snapshot of "gpt-4o"
+        let mini = prices.price("gpt-4o-mini-2024-07-18").unwrap();
+        assert_eq!((mini.input, mini.output), (0.15, 0.60));
+        assert!(prices.price("gpt-4o-audio-preview").is_none());
+    }
+
+    #[test]
+    fn configured_prices_override_and_extend_the_table() {
+        let ai = AiConfig {
+            pricing: BTreeMap::from([
+                ("gpt-4o".to_string(), ModelPrice { input: 1.0, output: 2.0 }),
+                ("qwen2.5:7b".to_string(), ModelPrice { input: 0.0, output: 0.0 }),
+            ]),
+            ..AiConfig::default()
+        };
+        let prices = PriceTable::from_config(&ai);
+
+        assert_eq!(prices.price("gpt-4o").map(|p| p.output), Some(2.0));
+        assert_eq!(prices.cost("qwen2.5:7b", TokenUsage { input: 5_000, output: 5_000 }), Some(0.0));
+    }
+
+    #[test]
+    fn cost_is_per_million_tokens_and_unknown_for_unpriced_models() {
+        let prices = PriceTable::default();
+        let usage = TokenUsage { input: 1_000_000, output: 500_000 };
+
+        let cost = prices.cost("gpt-4o-mini", usage).unwrap();
+        assert!((cost - 0.45).abs() < 1e-9);
+        assert_eq!(prices.cost("llama3", usage), None);
+    }
+}
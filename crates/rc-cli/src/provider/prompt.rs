@@ -0,0 +1,151 @@
+use super::TranslationRequest;
+use crate::templates::{self, DEFAULT_TEMPLATE};
+use crate::text_metrics::{char_count, field_limit};
+use std::collections::BTreeMap;
+
+/// Prompt for translating one field, shared by every chat-based provider: the request's template
+/// with its variables filled in
+pub fn build_prompt(request: &TranslationRequest) -> String {
+    let body = request
+        .template
+        .as_deref()
+        .or_else(|| templates::builtin(DEFAULT_TEMPLATE))
+        .unwrap_or("{{text}}");
+    let values = variables(request);
+    let mut prompt = templates::render(body, &values);
+
+    // A re-request has to say what was wrong, even with a template that doesn't place it
+    if !templates::uses(body, "shorten") && !values["shorten"].is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(&values["shorten"]);
+    }
+
+    prompt
+}
+
+/// Values of every template variable for one request; sections that don't apply are empty
+pub fn variables(request: &TranslationRequest) -> BTreeMap<&'static str, String> {
+    let mut values = BTreeMap::new();
+    values.insert("source_locale", request.source_locale.clone());
+    values.insert("target_locale", request.target_locale.clone());
+    values.insert("source_language", request.source_language.clone());
+    values.insert("target_language", request.target_language.clone());
+    values.insert("field", request.field.clone());
+    values.insert("char_limit", field_limit(&request.field).map(|limit| limit.to_string()).unwrap_or_default());
+    values.insert("text", request.text.clone());
+    values.insert("app_context", request.context.clone().unwrap_or_default());
+
+    values.insert(
+        "field_guidance",
+        field_guidance(&request.field)
+            .map(|guidance| format!("Field-specific guidance for \"{}\":\n{}", request.field, guidance))
+            .unwrap_or_default(),
+    );
+
+    let mut references = String::new();
+    if !request.references.is_empty() {
+        references.push_str("Previously approved translations of similar text (reuse their wording and terminology where it fits):");
+        for reference in &request.references {
+            references.push_str(&format!("\n- \"{}\" → \"{}\"", reference.source, reference.target));
+        }
+    }
+    values.insert("references", references);
+
+    let mut style = String::new();
+    if !request.style.is_empty() {
+        style.push_str(&format!("Style guide for {}:", request.target_language));
+        for instruction in &request.style {
+            style.push_str(&format!("\n- {}", instruction));
+        }
+    }
+    values.insert("style", style);
+
+    let mut glossary = Vec::new();
+    if !request.do_not_translate.is_empty() {
+        let terms: Vec<String> = request.do_not_translate.iter().map(|term| format!("\"{}\"", term)).collect();
+        glossary.push(format!("Never translate these terms; keep them exactly as written: {}", terms.join(", ")));
+    }
+    if !request.terms.is_empty() {
+        let mut fixed = "Always use these fixed translations:".to_string();
+        for (term, rendering) in &request.terms {
+            fixed.push_str(&format!("\n- \"{}\" → \"{}\"", term, rendering));
+        }
+        glossary.push(fixed);
+    }
+    values.insert("glossary", glossary.join("\n\n"));
+
+    let mut shorten = String::new();
+    if let Some(shortening) = &request.shorten {
+        shorten.push_str(&format!(
+            "Your previous translation was too long: \"{}\" ({} characters).\n\
+             The translation must be at most {} characters, counting every letter, space and punctuation mark. \
+             Rephrase or drop less important words instead of cutting the text off.",
+            shortening.previous,
+            char_count(&shortening.previous),
+            shortening.max_length
+        ));
+        if request.field == "keywords" {
+            shorten.push_str("\nKeep it a comma-separated list without spaces after commas, dropping the least important keywords first.");
+        }
+    }
+    values.insert("shorten", shorten);
+
+    values
+}
+
+fn field_guidance(field: &str) -> Option<&'static str> {
+    match field {
+        "name" => Some("- Keep it short and memorable\n- Consider cultural preferences for app naming"),
+        "description" => Some("- Be compelling and informative\n- Highlight key features and benefits"),
+        "keywords" => Some("- Translate concepts, not just words\n- Use terms people actually search for"),
+        "whatsNew" => Some("- Keep it concise and clear\n- Focus on user benefits"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Shortening;
+
+    fn request(field: &str, template: &str) -> TranslationRequest {
+        TranslationRequest {
+            field: field.to_string(),
+            text: "Hands Time".to_string(),
+            target_language: "German".to_string(),
+            template: Some(template.to_string()),
+            ..TranslationRequest::default()
+        }
+    }
+
+    fn shortening(previous: &str, max_length: usize) -> Option<Shortening> {
+        Some(Shortening { max_length, previous: previous.to_string() })
+    }
+
+    #[test]
+    fn first_requests_have_no_shorten_block() {
+        assert_eq!(build_prompt(&request("name", "Translate {{text}} into {{target_language}}")), "Translate Hands Time into German");
+    }
+
+    #[test]
+    fn shorten_block_is_appended_when_the_template_does_not_place_it() {
+        let mut request = request("subtitle", "Translate {{text}}");
+        request.shorten = shortening("Die Uhrzeit auf einen Blick, jederzeit", 30);
+
+        let prompt = build_prompt(&request);
+        assert!(prompt.starts_with("Translate Hands Time\n\nYour previous translation was too long: \"Die Uhrzeit auf einen Blick, jederzeit\" (38 characters)."));
+        assert!(prompt.contains("must be at most 30 characters"));
+        assert!(!prompt.contains("comma-separated"));
+    }
+
+    #[test]
+    fn shorten_block_goes_where_the_template_places_it() {
+        let mut request = request("keywords", "{{shorten}}\n---\n{{text}}");
+        request.shorten = shortening("uhr,zeit", 5);
+
+        let prompt = build_prompt(&request);
+        assert_eq!(prompt.matches("Your previous translation was too long").count(), 1);
+        assert!(prompt.ends_with("---\nHands Time"));
+        assert!(prompt.contains("comma-separated list without spaces after commas"));
+    }
+}
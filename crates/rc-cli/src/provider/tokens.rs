@@ -0,0 +1,109 @@
+use super::{build_prompt, TokenUsage, TranslationRequest};
+use anyhow::{Context, Result};
+use tiktoken_rs::CoreBPE;
+
+/// Tokens a chat API adds around a single user message
+const MESSAGE_OVERHEAD: u64 = 7;
+
+/// Counts tokens with the tokenizer of the configured model. Models tiktoken doesn't know
+/// (local and self-hosted ones) are counted with `o200k_base`, which is close enough for an estimate.
+pub struct TokenCounter {
+    bpe: CoreBPE,
+}
+
+impl TokenCounter {
+    pub fn for_model(model: &str) -> Result<Self> {
+        let bpe = tiktoken_rs::get_bpe_from_model(model)
+            .or_else(|_| tiktoken_rs::o200k_base())
+            .context("Failed to load tokenizer")?;
+        Ok(Self { bpe })
+    }
+
+    pub fn count(&self, text: &str) -> u64 {
+        self.bpe.encode_with_special_tokens(text).len() as u64
+    }
+
+    /// Expected tokens of one request: the full prompt, and a translation as long as the
+    /// existing translation of the field or, without one, the source text scaled by how many
+    /// more tokens the target language needs than English
+    pub fn estimate(&self, request: &TranslationRequest, existing: Option<&str>) -> TokenUsage {
+        let output = match existing.filter(|text| !text.trim().is_empty()) {
+            Some(text) => self.count(text),
+            None => (self.count(&request.text) as f64 * expansion(&request.target_locale)).ceil() as u64,
+        };
+        TokenUsage {
+            input: self.count(&build_prompt(request)) + MESSAGE_OVERHEAD,
+            output,
+        }
+    }
+}
+
+/// Tokens of a translation per token of English source text. Scripts the tokenizer has seen
+/// less of (Cyrillic, Greek, Devanagari, Thai) take noticeably more tokens for the same text.
+fn expansion(locale: &str) -> f64 {
+    match locale.split('-').next().unwrap_or(locale) {
+        "zh" => 0.9,
+        "ja" => 1.1,
+        "ko" | "es" | "it" | "pt" | "ca" => 1.2,
+        "fr" | "nl" | "vi" | "id" | "ms" => 1.25,
+        "de" | "sv" | "da" | "no" | "nb" | "ro" => 1.3,
+        "tr" | "ar" | "he" | "hr" => 1.4,
+        "pl" | "hu" | "fi" | "ru" | "uk" => 1.5,
+        "cs" | "sk" | "sl" | "th" => 1.6,
+        "hi" | "bn" | "ta" | "te" | "mr" | "gu" | "kn" | "ml" | "pa" | "or" | "ur" => 1.8,
+        "el" => 2.0,
+        _ => 1.3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(text: &str, target_locale: &str) -> TranslationRequest {
+        TranslationRequest {
+            field: "subtitle".to_string(),
+            text: text.to_string(),
+            source_locale: "en-US".to_string(),
+            target_locale: target_locale.to_string(),
+            template: Some("Translate: {{text}}".to_string()),
+            ..TranslationRequest::default()
+        }
+    }
+
+    #[test]
+    fn output_is_as_long_as_the_existing_translation() {
+        let counter = TokenCounter::for_model("gpt-4o-mini").unwrap();
+        let request = request("Tell time at a glance", "de-DE");
+        let existing = "Die Uhrzeit auf einen Blick, direkt auf dem Home-Bildschirm";
+
+        assert_eq!(counter.estimate(&request, Some(existing)).output, counter.count(existing));
+    }
+
+    #[test]
+    fn without_a_translation_the_source_is_scaled_by_language() {
+        let counter = TokenCounter::for_model("gpt-4o-mini").unwrap();
+        let text = "Tell time at a glance with widgets for your home screen";
+        let source_tokens = counter.count(text) as f64;
+
+        let german = counter.estimate(&request(text, "de-DE"), Some("  "));
+        assert_eq!(german.output, (source_tokens * 1.3).ceil() as u64);
+        let chinese = counter.estimate(&request(text, "zh-Hans"), None);
+        assert_eq!(chinese.output, (source_tokens * 0.9).ceil() as u64);
+    }
+
+    #[test]
+    fn input_is_the_rendered_prompt_plus_message_overhead() {
+        let counter = TokenCounter::for_model("gpt-4o-mini").unwrap();
+        let request = request("Tell time at a glance", "fr-FR");
+
+        let usage = counter.estimate(&request, None);
+        assert_eq!(usage.input, counter.count("Translate: Tell time at a glance") + MESSAGE_OVERHEAD);
+    }
+
+    #[test]
+    fn unknown_models_fall_back_to_a_default_tokenizer() {
+        let counter = TokenCounter::for_model("qwen2.5:7b").unwrap();
+        assert!(counter.count("Tell time at a glance") > 0);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::config::{Formality, StyleConfig};
+use crate::workspace::LocaleFields;
+use std::collections::BTreeSet;
+
+/// Fields written in full sentences; names, subtitles and keywords are left out of sentence checks
+const SENTENCE_FIELDS: [&str; 3] = ["description", "promotionalText", "whatsNew"];
+
+/// Quotation marks recognised when checking the configured quotation style
+const QUOTATION_MARKS: [char; 8] = ['"', '“', '”', '„', '«', '»', '「', '」'];
+
+/// Punctuation French typography separates from the preceding word with a non-breaking space
+const FRENCH_SPACED: [char; 5] = [':', ';', '!', '?', '»'];
+
+/// Polite sentence endings of Japanese です/ます style
+const JA_POLITE_ENDINGS: [&str; 9] = ["ます", "ました", "ません", "ましょう", "です", "でした", "でしょう", "ください", "下さい"];
+
+/// Words before "ton" that make it the noun "tone" rather than "your"
+const FRENCH_TONE_DETERMINERS: [&str; 6] = ["le", "un", "du", "au", "ce", "quel"];
+
+/// A style rule a translated field breaks
+#[derive(Debug, Clone)]
+pub struct StyleIssue {
+    pub field: String,
+    pub message: String,
+}
+
+/// Style instructions for the translation prompt of one locale
+pub fn prompt_instructions(locale: &str, style: &StyleConfig) -> Vec<String> {
+    let mut instructions = Vec::new();
+
+    if let Some(formality) = style.formality {
+        instructions.push(match (language(locale), formality) {
+            ("de", Formality::Formal) => "Address the reader formally with \"Sie\", never \"du\"".to_string(),
+            ("de", Formality::Informal) => "Address the reader informally with \"du\", never \"Sie\"".to_string(),
+            ("fr", Formality::Formal) => "Address the reader formally with \"vous\", never \"tu\"".to_string(),
+            ("fr", Formality::Informal) => "Address the reader informally with \"tu\", never \"vous\"".to_string(),
+            ("ja", Formality::Formal) => "Write every sentence in polite です/ます style".to_string(),
+            ("ja", Formality::Informal) => "Write in plain だ/である style, not です/ます".to_string(),
+            (_, Formality::Formal) => "Use a formal register to address the reader".to_string(),
+            (_, Formality::Informal) => "Use an informal, friendly register to address the reader".to_string(),
+        });
+    }
+    if let Some(tone) = &style.tone {
+        instructions.push(format!("Tone: {}", tone));
+    }
+    if let Some([open, close]) = quote_pair(style) {
+        instructions.push(format!("Use {}…{} as quotation marks", open, close));
+    }
+    if style.nbsp_before_punctuation {
+        instructions.push(
+            "Put a non-breaking space (U+00A0) before : ; ! ? and », and after «".to_string()
+        );
+    }
+    instructions.extend(style.notes.iter().cloned());
+
+    instructions
+}
+
+/// Check translated fields of one locale against its style settings
+pub fn check(locale: &str, style: &StyleConfig, translated: &LocaleFields) -> Vec<StyleIssue> {
+    let mut issues = Vec::new();
+
+    for (field, text) in translated {
+        let mut report = |message: String| issues.push(StyleIssue { field: field.clone(), message });
+
+        if let Some(formality) = style.formality {
+            match language(locale) {
+                "de" | "fr" => {
+                    let wrong = wrong_pronouns(language(locale), formality, text);
+                    if !wrong.is_empty() {
+                        report(format!("addresses the reader {} ({})", wrong_register(formality), wrong.join(", ")));
+                    }
+                }
+                "ja" if SENTENCE_FIELDS.contains(&field.as_str()) => {
+                    let mixed = japanese_mixed_sentences(formality, text);
+                    if let Some(first) = mixed.first() {
+                        report(format!("{} sentence(s) not in {} style, e.g. \"{}\"", mixed.len(), japanese_style_name(formality), first));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some([open, close]) = quote_pair(style) {
+            let other: BTreeSet<char> = text.chars().filter(|c| QUOTATION_MARKS.contains(c) && *c != open && *c != close).collect();
+            if !other.is_empty() {
+                let marks: String = other.into_iter().collect();
+                report(format!("quotation marks {} instead of {}…{}", marks, open, close));
+            }
+        }
+
+        if style.nbsp_before_punctuation {
+            let missing = missing_nbsp(text);
+            if let Some(first) = missing.first() {
+                report(format!("{} place(s) missing a non-breaking space, e.g. \"{}\"", missing.len(), first));
+            }
+        }
+    }
+
+    issues
+}
+
+fn language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+fn quote_pair(style: &StyleConfig) -> Option<[char; 2]> {
+    let mut chars = style.quotes.as_deref()?.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(open), Some(close), None) => Some([open, close]),
+        _ => None,
+    }
+}
+
+/// German and French pronouns of the register the locale is not supposed to use
+fn wrong_pronouns(language: &str, formality: Formality, text: &str) -> Vec<String> {
+    // German formal pronouns are capitalised; lowercase "sie"/"ihr" mean she/they/her
+    let (candidates, case_sensitive): (&[&str], bool) = match (language, formality) {
+        ("de", Formality::Formal) => (&["du", "dich", "dir", "dein", "deine", "deinen", "deinem", "deiner", "deines"], false),
+        ("de", Formality::Informal) => (&["Sie", "Ihnen", "Ihr", "Ihre", "Ihren", "Ihrem", "Ihrer", "Ihres"], true),
+        ("fr", Formality::Formal) => (&["tu", "toi", "ton", "ta", "tes", "te"], false),
+        ("fr", Formality::Informal) => (&["vous", "votre", "vos"], false),
+        _ => return Vec::new(),
+    };
+
+    // Whole words only, so "ton" doesn't match inside "bouton"
+    let mut found: Vec<String> = Vec::new();
+    for sentence in text.split(['.', '!', '?', ':', '\n']) {
+        let words: Vec<&str> = sentence.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+        for (index, &word) in words.iter().enumerate() {
+            let lowercase = word.to_lowercase();
+            // "le ton", "un ton" are the noun "tone"
+            let tone_noun = language == "fr"
+                && lowercase == "ton"
+                && index > 0
+                && FRENCH_TONE_DETERMINERS.contains(&words[index - 1].to_lowercase().as_str());
+            let matched = if case_sensitive {
+                // A capitalised "Sie" opening a sentence may just mean "they"
+                index > 0 && candidates.contains(&word)
+            } else {
+                candidates.contains(&lowercase.as_str()) && !tone_noun
+            };
+            if matched && !found.iter().any(|f| f.eq_ignore_ascii_case(word)) {
+                found.push(word.to_string());
+            }
+        }
+    }
+    found
+}
+
+/// Japanese sentences ending in "。" whose ending doesn't match the configured style
+fn japanese_mixed_sentences(formality: Formality, text: &str) -> Vec<String> {
+    let mut sentences: Vec<&str> = text.split('。').collect();
+    // The text after the last "。" is not a finished sentence
+    sentences.pop();
+
+    sentences
+        .into_iter()
+        .map(|sentence| sentence.trim().trim_end_matches(['」', '）', ')']))
+        .filter(|sentence| !sentence.is_empty())
+        .filter(|sentence| {
+            let polite = JA_POLITE_ENDINGS.iter().any(|ending| sentence.ends_with(ending));
+            match formality {
+                Formality::Formal => !polite,
+                Formality::Informal => polite,
+            }
+        })
+        .map(|sentence| {
+            let tail: Vec<char> = sentence.chars().rev().take(12).collect();
+            format!("…{}。", tail.into_iter().rev().collect::<String>())
+        })
+        .collect()
+}
+
+/// How text breaking the configured formality addresses the reader
+fn wrong_register(formality: Formality) -> &'static str {
+    match formality {
+        Formality::Formal => "informally",
+        Formality::Informal => "formally",
+    }
+}
+
+fn japanese_style_name(formality: Formality) -> &'static str {
+    match formality {
+        Formality::Formal => "です/ます",
+        Formality::Informal => "だ/である",
+    }
+}
+
+/// Snippets around French punctuation preceded by an ordinary space or by no space at all
+fn missing_nbsp(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let is_nbsp = |c: char| c == '\u{a0}' || c == '\u{202f}';
+    let mut found = Vec::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        let before = if i > 0 { Some(chars[i - 1]) } else { None };
+        let after = chars.get(i + 1).copied();
+        let missing = if FRENCH_SPACED.contains(&c) {
+            match before {
+                // Start of text, "?!", "...!" and the like
+                None => false,
+                Some(b) if is_nbsp(b) || FRENCH_SPACED.contains(&b) || b == '.' || b == '\n' => false,
+                // Times (10:30) and URLs (https://)
+                Some(b) if c == ':' && (b.is_ascii_digit() && after.is_some_and(|a| a.is_ascii_digit())) => false,
+                Some(_) if c == ':' && after == Some('/') => false,
+                Some(_) => true,
+            }
+        } else if c == '«' {
+            after.is_some_and(|a| !is_nbsp(a))
+        } else {
+            false
+        };
+
+        if missing {
+            let start = i.saturating_sub(10);
+            let end = (i + 2).min(chars.len());
+            found.push(chars[start..end].iter().collect::<String>().replace('\n', " ").trim().to_string());
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> LocaleFields {
+        pairs.iter().map(|(field, value)| (field.to_string(), value.to_string())).collect()
+    }
+
+    fn formality(formality: Formality) -> StyleConfig {
+        StyleConfig { formality: Some(formality), ..StyleConfig::default() }
+    }
+
+    fn messages(issues: &[StyleIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.message.as_str()).collect()
+    }
+
+    #[test]
+    fn french_pronouns_are_matched_as_whole_words() {
+        let style = formality(Formality::Formal);
+        let clean = fields(&[("description", "Touchez le bouton. Le ton reste sobre, avec un ton léger.")]);
+        assert!(check("fr-FR", &style, &clean).is_empty());
+
+        let informal = fields(&[("description", "Choisis ton thème. Ton écran, tes widgets.")]);
+        assert_eq!(messages(&check("fr-FR", &style, &informal)), ["addresses the reader informally (ton, tes)"]);
+    }
+
+    #[test]
+    fn informal_french_allows_ton_and_flags_vous() {
+        let style = formality(Formality::Informal);
+        let text = fields(&[("description", "Choisis ton thème. Changez votre fond d'écran.")]);
+        assert_eq!(messages(&check("fr-FR", &style, &text)), ["addresses the reader formally (votre)"]);
+    }
+
+    #[test]
+    fn german_sie_opening_a_sentence_is_not_formal() {
+        let style = formality(Formality::Informal);
+        let text = fields(&[("description", "Widgets zeigen die Zeit. Sie sind schön. Passen Sie Ihr Design an.")]);
+        assert_eq!(messages(&check("de-DE", &style, &text)), ["addresses the reader formally (Sie, Ihr)"]);
+    }
+
+    #[test]
+    fn japanese_sentences_must_match_the_register() {
+        let style = formality(Formality::Formal);
+        let text = fields(&[("description", "時間を確認できます。ウィジェットも使える。"), ("name", "時計だ。")]);
+        let issues = check("ja", &style, &text);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "description");
+        assert!(issues[0].message.starts_with("1 sentence(s) not in です/ます style"));
+    }
+
+    #[test]
+    fn other_quotation_marks_are_reported_once_each() {
+        let style = StyleConfig { quotes: Some("「」".to_string()), ..StyleConfig::default() };
+        let text = fields(&[("description", "“Hands” and «Time» and “Clock” and 「時計」")]);
+        assert_eq!(messages(&check("ja", &style, &text)), ["quotation marks «»“” instead of 「…」"]);
+    }
+
+    #[test]
+    fn french_punctuation_needs_a_non_breaking_space() {
+        let style = StyleConfig { nbsp_before_punctuation: true, ..StyleConfig::default() };
+        let spaced = fields(&[("description", "Nouveau\u{a0}! Widgets à 10:30, voir https://example.com")]);
+        assert!(check("fr-FR", &style, &spaced).is_empty());
+
+        let unspaced = fields(&[("description", "Nouveau ! Quoi? «\u{a0}Heure\u{a0}»")]);
+        assert_eq!(messages(&check("fr-FR", &style, &unspaced)), ["2 place(s) missing a non-breaking space, e.g. \"Nouveau !\""]);
+    }
+}
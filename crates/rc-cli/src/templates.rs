@@ -0,0 +1,541 @@
+use crate::config::{AiConfig, TemplateMapping};
+use anyhow::{Context, Result};
+use chrono::{NaiveDateTime, Utc};
+use std::collections::btree_map::{BTreeMap, Entry};
+use std::fs;
+use std::path::PathBuf;
+
+/// Template used for fields no other template is chosen for
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// File names of earlier template versions in `.rosetta/templates/history/<name>/`
+const VERSION_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Variables a template can use as `{{name}}`
+pub const VARIABLES: [&str; 13] = [
+    "source_locale",
+    "target_locale",
+    "source_language",
+    "target_language",
+    "field",
+    "char_limit",
+    "text",
+    "app_context",
+    "field_guidance",
+    "references",
+    "style",
+    "glossary",
+    "shorten",
+];
+
+/// Templates that ship with the CLI; a project template with the same name replaces one
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    ("default", include_str!("../templates/default.txt")),
+    ("concise", include_str!("../templates/concise.txt")),
+    ("keywords", include_str!("../templates/keywords.txt")),
+    ("release-notes", include_str!("../templates/release-notes.txt")),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateOrigin {
+    Builtin,
+    Project,
+    /// A project template replacing the built-in one of the same name
+    Override,
+}
+
+/// A prompt with `{{variable}}` placeholders, filled in for every field `translate` sends
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub body: String,
+    pub origin: TemplateOrigin,
+}
+
+impl Template {
+    /// The first `{{! comment }}` of the template
+    pub fn description(&self) -> Option<&str> {
+        tags(&self.body).ok()?.into_iter().find_map(|tag| match tag {
+            Tag::Comment(text) => Some(text),
+            Tag::Variable(_) => None,
+        })
+    }
+}
+
+pub fn builtin(name: &str) -> Option<&'static str> {
+    BUILTIN_TEMPLATES.iter().find(|(builtin, _)| *builtin == name).map(|(_, body)| *body)
+}
+
+enum Tag<'a> {
+    Variable(&'a str),
+    Comment(&'a str),
+}
+
+/// Every `{{...}}` of a template, in order
+fn tags(body: &str) -> Result<Vec<Tag<'_>>> {
+    let mut tags = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .with_context(|| format!("Unclosed '{{{{' near \"{}\"", after.lines().next().unwrap_or("").trim()))?;
+        let inner = after[..end].trim();
+        tags.push(match inner.strip_prefix('!') {
+            Some(comment) => Tag::Comment(comment.trim()),
+            None => Tag::Variable(inner),
+        });
+        rest = &after[end + 2..];
+    }
+    Ok(tags)
+}
+
+/// Whether a template places `variable` itself
+pub fn uses(body: &str, variable: &str) -> bool {
+    tags(body).is_ok_and(|tags| tags.iter().any(|tag| matches!(tag, Tag::Variable(name) if *name == variable)))
+}
+
+/// Check that a template only uses known variables and includes the text to translate
+pub fn validate(body: &str) -> Result<()> {
+    for tag in tags(body)? {
+        if let Tag::Variable(name) = tag {
+            if !VARIABLES.contains(&name) {
+                anyhow::bail!("Unknown template variable {{{{{}}}}}; available: {}", name, VARIABLES.join(", "));
+            }
+        }
+    }
+    if !uses(body, "text") {
+        anyhow::bail!("Template must include {{{{text}}}}, the text to translate");
+    }
+    Ok(())
+}
+
+/// Fill in a validated template. Comments are dropped, and so are lines holding nothing but a
+/// variable that is empty, along with the blank lines around them, so optional sections can sit
+/// on lines of their own. Values are inserted exactly as they are.
+pub fn render(body: &str, values: &BTreeMap<&str, String>) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in body.lines() {
+        let rendered = render_line(line, values);
+        let standalone = line.trim().starts_with("{{") && line.trim().ends_with("}}") && tags(line).is_ok_and(|tags| tags.len() == 1);
+        if standalone && rendered.trim().is_empty() {
+            continue;
+        }
+        // One blank line between sections, however many the dropped sections left behind
+        let blank = line.trim().is_empty();
+        if blank && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(if blank { String::new() } else { rendered });
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+fn render_line(line: &str, values: &BTreeMap<&str, String>) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let inner = after[..end].trim();
+        if !inner.starts_with('!') {
+            output.push_str(values.get(inner).map(|v| v.as_str()).unwrap_or(""));
+        }
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Template names are used as file names
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!("Invalid template name '{}': use lowercase letters, digits, '-' and '_'", name);
+    }
+    Ok(())
+}
+
+/// Prompt templates of the project in `.rosetta/templates/<name>.txt`, on top of the built-in set
+pub struct TemplateStore {
+    dir: PathBuf,
+}
+
+impl TemplateStore {
+    pub fn open() -> Result<Self> {
+        let mut dir = std::env::current_dir()
+            .context("Failed to get current directory")?;
+        dir.push(".rosetta");
+        dir.push("templates");
+        Ok(Self { dir })
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.txt", name))
+    }
+
+    /// Whether the project has its own template of this name
+    pub fn is_saved(&self, name: &str) -> bool {
+        self.path(name).is_file()
+    }
+
+    pub fn get(&self, name: &str) -> Result<Template> {
+        validate_name(name)?;
+        let path = self.path(name);
+        if path.is_file() {
+            let body = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read template: {}", path.display()))?;
+            validate(&body)
+                .with_context(|| format!("Invalid template: {}", path.display()))?;
+            let origin = if builtin(name).is_some() { TemplateOrigin::Override } else { TemplateOrigin::Project };
+            return Ok(Template { name: name.to_string(), body, origin });
+        }
+
+        match builtin(name) {
+            Some(body) => Ok(Template {
+                name: name.to_string(),
+                body: body.to_string(),
+                origin: TemplateOrigin::Builtin,
+            }),
+            None => anyhow::bail!(
+                "Template '{}' not found; run 'rosetta-connect template list' to see the available templates",
+                name
+            ),
+        }
+    }
+
+    /// Built-in and project templates, sorted by name
+    pub fn list(&self) -> Result<Vec<Template>> {
+        let mut names: Vec<String> = BUILTIN_TEMPLATES.iter().map(|(name, _)| name.to_string()).collect();
+        if self.dir.is_dir() {
+            let entries = fs::read_dir(&self.dir)
+                .with_context(|| format!("Failed to read templates directory: {}", self.dir.display()))?;
+            for entry in entries {
+                let path = entry.context("Failed to read templates directory entry")?.path();
+                if path.extension().is_some_and(|ext| ext == "txt") {
+                    if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+        names.sort();
+        names.dedup();
+
+        names.iter().map(|name| self.get(name)).collect()
+    }
+
+    /// Earlier versions of a project template, one file per save
+    fn history_dir(&self, name: &str) -> PathBuf {
+        self.dir.join("history").join(name)
+    }
+
+    /// Validate and write a project template; the version it replaces is kept in the history
+    pub fn save(&self, name: &str, body: &str) -> Result<PathBuf> {
+        validate_name(name)?;
+        validate(body)?;
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(name);
+
+        if let Ok(replaced) = fs::read_to_string(&path) {
+            if replaced != body {
+                let history = self.history_dir(name);
+                fs::create_dir_all(&history)
+                    .with_context(|| format!("Failed to create {}", history.display()))?;
+                let version = history.join(format!("{}.txt", Utc::now().format(VERSION_FORMAT)));
+                fs::write(&version, replaced)
+                    .with_context(|| format!("Failed to write template version: {}", version.display()))?;
+            }
+        }
+
+        fs::write(&path, body)
+            .with_context(|| format!("Failed to write template: {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// The version a template replaced: its last saved version, or for a project template that
+    /// replaces a built-in one, the built-in template
+    pub fn previous_version(&self, name: &str) -> Result<Option<TemplateVersion>> {
+        let history = self.history_dir(name);
+        if history.is_dir() {
+            let mut versions: Vec<PathBuf> = fs::read_dir(&history)
+                .with_context(|| format!("Failed to read {}", history.display()))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect();
+            versions.sort();
+            if let Some(path) = versions.pop() {
+                let body = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read template version: {}", path.display()))?;
+                let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+                let saved = NaiveDateTime::parse_from_str(stem, VERSION_FORMAT)
+                    .map(|saved| saved.format("%Y-%m-%d %H:%M UTC").to_string())
+                    .unwrap_or_else(|_| stem.to_string());
+                return Ok(Some(TemplateVersion { label: format!("previous ({})", saved), body }));
+            }
+        }
+
+        Ok(builtin(name)
+            .filter(|_| self.is_saved(name))
+            .map(|body| TemplateVersion { label: "built-in".to_string(), body: body.to_string() }))
+    }
+
+    /// Delete a project template and its earlier versions
+    pub fn remove(&self, name: &str) -> Result<()> {
+        let path = self.path(name);
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to delete template: {}", path.display()))?;
+        let history = self.history_dir(name);
+        if history.is_dir() {
+            fs::remove_dir_all(&history)
+                .with_context(|| format!("Failed to delete template history: {}", history.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// An earlier body of a template
+#[derive(Debug, Clone)]
+pub struct TemplateVersion {
+    pub label: String,
+    pub body: String,
+}
+
+/// Picks the template of every locale and field. The most specific choice wins:
+/// `--template`, then `[ai.templates.<locale>]` by field and its "default", then `[ai.templates]`
+/// by field and its "default", then the built-in default template.
+pub struct TemplateResolver {
+    mapping: BTreeMap<String, TemplateMapping>,
+    forced: Option<String>,
+    templates: BTreeMap<String, Template>,
+}
+
+impl TemplateResolver {
+    /// Load every template the configuration refers to, so a missing one fails before any request
+    pub fn new(store: &TemplateStore, ai: &AiConfig, forced: Option<&str>) -> Result<Self> {
+        let mut names = vec![DEFAULT_TEMPLATE.to_string()];
+        names.extend(forced.map(str::to_string));
+        for mapping in ai.templates.values() {
+            match mapping {
+                TemplateMapping::Template(name) => names.push(name.clone()),
+                TemplateMapping::Locale(fields) => names.extend(fields.values().cloned()),
+            }
+        }
+
+        let mut templates = BTreeMap::new();
+        for name in names {
+            if let Entry::Vacant(entry) = templates.entry(name) {
+                let template = store.get(entry.key())
+                    .with_context(|| format!("Failed to load prompt template '{}'", entry.key()))?;
+                entry.insert(template);
+            }
+        }
+
+        Ok(Self {
+            mapping: ai.templates.clone(),
+            forced: forced.map(str::to_string),
+            templates,
+        })
+    }
+
+    /// The template for a field of a locale, and the setting that chose it
+    pub fn choose(&self, locale: &str, field: &str) -> (&Template, String) {
+        let (name, source) = self.choose_name(locale, field);
+        (&self.templates[name], source)
+    }
+
+    pub fn resolve(&self, locale: &str, field: &str) -> &Template {
+        self.choose(locale, field).0
+    }
+
+    fn choose_name(&self, locale: &str, field: &str) -> (&str, String) {
+        if let Some(name) = &self.forced {
+            return (name, "the command line".to_string());
+        }
+
+        if let Some(TemplateMapping::Locale(fields)) = self.mapping.get(locale) {
+            for key in [field, "default"] {
+                if let Some(name) = fields.get(key) {
+                    return (name, format!("[ai.templates.{}].{}", locale, key));
+                }
+            }
+        }
+        for key in [field, "default"] {
+            if let Some(TemplateMapping::Template(name)) = self.mapping.get(key) {
+                return (name, format!("[ai.templates].{}", key));
+            }
+        }
+
+        (DEFAULT_TEMPLATE, "built-in default".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&'static str, &str)]) -> BTreeMap<&'static str, String> {
+        pairs.iter().map(|(name, value)| (*name, value.to_string())).collect()
+    }
+
+    fn error(body: &str) -> String {
+        validate(body).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn tags_are_variables_and_comments_in_order() {
+        let tags = tags("{{! Short fields }}Translate {{ text }} to {{target_language}}").unwrap();
+        let parsed: Vec<(bool, &str)> = tags
+            .iter()
+            .map(|tag| match tag {
+                Tag::Comment(text) => (true, *text),
+                Tag::Variable(name) => (false, *name),
+            })
+            .collect();
+        assert_eq!(parsed, [(true, "Short fields"), (false, "text"), (false, "target_language")]);
+
+        assert!(uses("Translate {{ text }}", "text"));
+        assert!(!uses("{{! mentions text }}{{style}}", "text"));
+    }
+
+    #[test]
+    fn description_is_the_first_comment() {
+        let template = Template {
+            name: "short".to_string(),
+            body: "{{! Keep it short }}\n{{text}}\n{{! second }}".to_string(),
+            origin: TemplateOrigin::Project,
+        };
+        assert_eq!(template.description(), Some("Keep it short"));
+        assert!(builtin(DEFAULT_TEMPLATE).is_some());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_variables_unclosed_tags_and_missing_text() {
+        assert!(validate("{{style}}\nTranslate {{text}} ({{char_limit}} characters)").is_ok());
+        assert!(error("{{text}} for {{app_name}}").starts_with("Unknown template variable {{app_name}}; available: source_locale"));
+        assert_eq!(error("Translate {{text}} to {{target_language"), "Unclosed '{{' near \"target_language\"");
+        assert_eq!(error("Translate {{! the text }} please"), "Template must include {{text}}, the text to translate");
+    }
+
+    #[test]
+    fn every_builtin_template_is_valid() {
+        for (name, body) in BUILTIN_TEMPLATES {
+            assert!(validate(body).is_ok(), "built-in template {} is invalid", name);
+        }
+    }
+
+    #[test]
+    fn empty_standalone_variables_drop_their_line_and_the_gap() {
+        let body = "Translate to {{target_language}}.\n\n{{style}}\n\n{{glossary}}\n\nText:\n{{text}}\n";
+
+        let without = render(body, &values(&[("target_language", "German"), ("text", "Hands Time")]));
+        assert_eq!(without, "Translate to German.\n\nText:\nHands Time");
+
+        let with = render(body, &values(&[("target_language", "German"), ("style", "Use Sie"), ("text", "Hands Time")]));
+        assert_eq!(with, "Translate to German.\n\nUse Sie\n\nText:\nHands Time");
+    }
+
+    #[test]
+    fn comments_are_dropped_and_values_inserted_verbatim() {
+        let body = "{{! Internal note }}\nSay \"{{text}}\" {{! inline }}in {{target_language}}\n";
+        let rendered = render(body, &values(&[("text", "{{style}} & \"quotes\""), ("target_language", "French")]));
+        assert_eq!(rendered, "Say \"{{style}} & \"quotes\"\" in French");
+    }
+
+    #[test]
+    fn variables_without_a_value_render_empty_inside_a_line() {
+        let rendered = render("Context: {{app_context}}.\n{{text}}", &values(&[("text", "Hands Time")]));
+        assert_eq!(rendered, "Context: .\nHands Time");
+    }
+
+    #[test]
+    fn template_names_must_be_usable_as_file_names() {
+        assert!(validate_name("release-notes_v2").is_ok());
+        for name in ["", "Release", "../default", "with space"] {
+            assert!(validate_name(name).is_err(), "{:?} should be rejected", name);
+        }
+    }
+
+    fn resolver(mapping: &[(&str, TemplateMapping)], forced: Option<&str>) -> Result<TemplateResolver> {
+        // A project without templates of its own has only the built-in ones
+        let store = TemplateStore { dir: std::env::temp_dir().join("rosetta-no-templates") };
+        let ai = AiConfig {
+            templates: mapping.iter().map(|(key, mapping)| (key.to_string(), mapping.clone())).collect(),
+            ..AiConfig::default()
+        };
+        TemplateResolver::new(&store, &ai, forced)
+    }
+
+    fn template(name: &str) -> TemplateMapping {
+        TemplateMapping::Template(name.to_string())
+    }
+
+    fn locale(fields: &[(&str, &str)]) -> TemplateMapping {
+        TemplateMapping::Locale(fields.iter().map(|(field, name)| (field.to_string(), name.to_string())).collect())
+    }
+
+    fn chosen(resolver: &TemplateResolver, locale: &str, field: &str) -> (String, String) {
+        let (template, source) = resolver.choose(locale, field);
+        (template.name.clone(), source)
+    }
+
+    fn pair(name: &str, source: &str) -> (String, String) {
+        (name.to_string(), source.to_string())
+    }
+
+    #[test]
+    fn without_configuration_every_field_uses_the_default_template() {
+        let resolver = resolver(&[], None).unwrap();
+        assert_eq!(chosen(&resolver, "ja", "keywords"), pair("default", "built-in default"));
+    }
+
+    #[test]
+    fn locale_tables_win_over_field_templates_and_defaults() {
+        let resolver = resolver(
+            &[
+                ("keywords", template("keywords")),
+                ("default", template("concise")),
+                ("ja", locale(&[("keywords", "release-notes"), ("default", "default")])),
+                ("de-DE", locale(&[("whatsNew", "release-notes")])),
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(chosen(&resolver, "ja", "keywords"), pair("release-notes", "[ai.templates.ja].keywords"));
+        assert_eq!(chosen(&resolver, "ja", "description"), pair("default", "[ai.templates.ja].default"));
+        // A locale table without its own "default" falls back to the project-wide settings
+        assert_eq!(chosen(&resolver, "de-DE", "keywords"), pair("keywords", "[ai.templates].keywords"));
+        assert_eq!(chosen(&resolver, "fr-FR", "subtitle"), pair("concise", "[ai.templates].default"));
+    }
+
+    #[test]
+    fn command_line_template_wins_over_configuration() {
+        let resolver = resolver(
+            &[("keywords", template("keywords")), ("ja", locale(&[("default", "release-notes")]))],
+            Some("concise"),
+        )
+        .unwrap();
+
+        assert_eq!(chosen(&resolver, "ja", "keywords"), pair("concise", "the command line"));
+        assert_eq!(chosen(&resolver, "fr-FR", "name"), pair("concise", "the command line"));
+    }
+
+    #[test]
+    fn unknown_templates_fail_before_any_request() {
+        let error = resolver(&[("ja", locale(&[("keywords", "missing")]))], None).err().unwrap();
+        assert_eq!(error.to_string(), "Failed to load prompt template 'missing'");
+        assert!(format!("{:#}", error).contains("Template 'missing' not found"));
+
+        let error = resolver(&[], Some("nope")).err().unwrap();
+        assert_eq!(error.to_string(), "Failed to load prompt template 'nope'");
+    }
+}
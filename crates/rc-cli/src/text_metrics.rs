@@ -0,0 +1,262 @@
+use unicode_linebreak::linebreaks;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// App Store Connect character limits per field
+pub const FIELD_LIMITS: [(&str, usize); 6] = [
+    ("name", 30),
+    ("subtitle", 30),
+    ("promotionalText", 170),
+    ("description", 4000),
+    ("keywords", 100),
+    ("whatsNew", 4000),
+];
+
+/// Character limit App Store Connect enforces for a metadata field
+pub fn field_limit(field: &str) -> Option<usize> {
+    FIELD_LIMITS.iter().find(|(f, _)| *f == field).map(|(_, limit)| *limit)
+}
+
+/// A translated field that came back longer than its limit, and how shortening it went
+#[derive(Debug, Clone)]
+pub struct LengthFix {
+    pub field: String,
+    pub limit: usize,
+    /// Length of the first translation
+    pub original: usize,
+    /// Length of the shortest translation received
+    pub length: usize,
+    /// Shortening requests made
+    pub attempts: usize,
+}
+
+impl LengthFix {
+    pub fn resolved(&self) -> bool {
+        self.length <= self.limit
+    }
+}
+
+/// Count user-perceived characters (extended grapheme clusters), the way App Store Connect does.
+/// `"日本語"` is 3 characters and a flag emoji is 1, not 9 and 8 bytes.
+pub fn char_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Shorten `text` to at most `max` characters, ending with "..." when something was cut and the
+/// ellipsis fits. Never splits a grapheme cluster, so it is safe on CJK, emoji and combining marks.
+pub fn truncate(text: &str, max: usize) -> String {
+    if char_count(text) <= max {
+        return text.to_string();
+    }
+    if max < 3 {
+        return prefix(text, max).to_string();
+    }
+
+    format!("{}...", prefix(text, max.saturating_sub(3)))
+}
+
+/// The first `count` characters of `text`, without splitting a grapheme cluster
+pub fn prefix(text: &str, count: usize) -> &str {
+    match text.grapheme_indices(true).nth(count) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// Terminal columns `text` occupies: East Asian wide characters and emoji take two columns,
+/// combining marks and zero-width joiners take none.
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+fn grapheme_width(grapheme: &str) -> usize {
+    // Multi-codepoint emoji (flags, ZWJ families, VS16 presentation) render as a single wide glyph
+    let mut chars = grapheme.chars();
+    if chars.next().is_some() && chars.next().is_some()
+        && grapheme.chars().any(|c| c == '\u{200D}' || c == '\u{FE0F}' || ('\u{1F1E6}'..='\u{1F1FF}').contains(&c))
+    {
+        return 2;
+    }
+    UnicodeWidthStr::width(grapheme)
+}
+
+/// Wrap `text` into lines of at most `width` terminal columns.
+/// Lines break only at Unicode line-break opportunities (UAX #14), so CJK text without spaces wraps
+/// between characters but never before closing punctuation such as `。` or `」`.
+/// Explicit newlines are kept; a segment wider than a whole line is split between graphemes.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        wrap_paragraph(paragraph.trim_end(), width, &mut lines);
+    }
+    lines
+}
+
+fn wrap_paragraph(paragraph: &str, width: usize, lines: &mut Vec<String>) {
+    if paragraph.is_empty() {
+        lines.push(String::new());
+        return;
+    }
+
+    let mut line = String::new();
+    let mut line_width = 0;
+    let mut start = 0;
+
+    for (end, _) in linebreaks(paragraph) {
+        let segment = &paragraph[start..end];
+        start = end;
+
+        // Trailing spaces may hang past the edge, so only the visible part has to fit
+        let visible_width = display_width(segment.trim_end());
+        if line_width + visible_width > width && !line.is_empty() {
+            lines.push(line.trim_end().to_string());
+            line.clear();
+            line_width = 0;
+        }
+
+        if visible_width > width {
+            for grapheme in segment.graphemes(true) {
+                let cell_width = grapheme_width(grapheme);
+                if line_width + cell_width > width && !line.is_empty() {
+                    lines.push(line.trim_end().to_string());
+                    line.clear();
+                    line_width = 0;
+                }
+                line.push_str(grapheme);
+                line_width += cell_width;
+            }
+        } else {
+            line.push_str(segment);
+            line_width += display_width(segment);
+        }
+    }
+
+    if !line.trim().is_empty() {
+        lines.push(line.trim_end().to_string());
+    }
+}
+
+/// Left-align `text` in a column `width` terminal columns wide.
+/// `format!("{:<w$}")` pads by `char`s, which misaligns CJK and emoji.
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// Fit `text` on one line of `width` columns, ending with "…" when it doesn't fit,
+/// the way iOS truncates single-line labels
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        let cell_width = grapheme_width(grapheme);
+        if used + cell_width + 1 > width {
+            break;
+        }
+        truncated.push_str(grapheme);
+        used += cell_width;
+    }
+    format!("{}…", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_graphemes_not_bytes_or_chars() {
+        assert_eq!(char_count("日本語"), 3);
+        assert_eq!(char_count("🇯🇵"), 1);
+        assert_eq!(char_count("👨‍👩‍👧"), 1);
+        assert_eq!(char_count("e\u{301}"), 1);
+        assert_eq!(char_count(""), 0);
+    }
+
+    #[test]
+    fn truncate_keeps_short_text() {
+        assert_eq!(truncate("abc", 3), "abc");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn truncate_ends_with_ellipsis_within_max() {
+        assert_eq!(truncate("abcdef", 5), "ab...");
+        assert_eq!(truncate("abcdef", 3), "...");
+        assert_eq!(truncate("日本語のテキスト", 6), "日本語...");
+    }
+
+    #[test]
+    fn truncate_drops_ellipsis_that_does_not_fit() {
+        assert_eq!(truncate("abcdef", 2), "ab");
+        assert_eq!(truncate("abcdef", 1), "a");
+        assert_eq!(truncate("abcdef", 0), "");
+        for max in 0..8 {
+            assert!(char_count(&truncate("🇯🇵🇫🇷🇩🇪🇪🇸🇮🇹🇰🇷🇨🇳", max)) <= max);
+        }
+    }
+
+    #[test]
+    fn prefix_never_splits_a_grapheme() {
+        assert_eq!(prefix("🇯🇵🇫🇷", 1), "🇯🇵");
+        assert_eq!(prefix("e\u{301}x", 1), "e\u{301}");
+        assert_eq!(prefix("ab", 5), "ab");
+    }
+
+    #[test]
+    fn display_width_of_wide_and_zero_width_text() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本"), 4);
+        assert_eq!(display_width("🇯🇵"), 2);
+        assert_eq!(display_width("👨‍👩‍👧"), 2);
+        assert_eq!(display_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn field_limits() {
+        assert_eq!(field_limit("subtitle"), Some(30));
+        assert_eq!(field_limit("keywords"), Some(100));
+        assert_eq!(field_limit("screenshots"), None);
+    }
+
+    #[test]
+    fn wrap_breaks_between_words_and_keeps_newlines() {
+        assert_eq!(wrap("one two three", 8), ["one two", "three"]);
+        assert_eq!(wrap("one\n\ntwo", 10), ["one", "", "two"]);
+    }
+
+    #[test]
+    fn wrap_cjk_never_starts_a_line_with_closing_punctuation() {
+        let lines = wrap("時計を表示します。", 8);
+        assert!(lines.iter().all(|line| display_width(line) <= 8), "{:?}", lines);
+        assert!(lines.iter().all(|line| !line.starts_with('。')), "{:?}", lines);
+        assert_eq!(lines.concat(), "時計を表示します。");
+    }
+
+    #[test]
+    fn wrap_splits_segments_wider_than_a_line() {
+        assert_eq!(wrap("abcdefgh", 3), ["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn pad_to_width_pads_by_columns() {
+        assert_eq!(pad_to_width("日本", 6), "日本  ");
+        assert_eq!(pad_to_width("toolong", 3), "toolong");
+    }
+
+    #[test]
+    fn truncate_to_width_fits_the_ellipsis() {
+        assert_eq!(truncate_to_width("Hands Time", 20), "Hands Time");
+        assert_eq!(truncate_to_width("Hands Time", 6), "Hands…");
+        assert_eq!(truncate_to_width("日本語の名前", 7), "日本語…");
+        assert_eq!(truncate_to_width("abc", 1), "…");
+        assert_eq!(truncate_to_width("abc", 0), "");
+    }
+}
@@ -0,0 +1,375 @@
+use crate::text_metrics::{char_count, FIELD_LIMITS};
+use crate::workspace::LocaleFields;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Fields App Store Connect requires before a version can be submitted
+const REQUIRED_FIELDS: [&str; 3] = ["name", "description", "keywords"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// One finding of the rules engine
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub locale: String,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, locale: &str, field: Option<&str>, message: String) -> Self {
+        Self {
+            severity,
+            locale: locale.to_string(),
+            field: field.map(|f| f.to_string()),
+            message,
+        }
+    }
+}
+
+/// Everything the rules look at, gathered from the local workspace
+pub struct ValidationInput<'a> {
+    pub default_locale: &'a str,
+    /// Locales the project is expected to ship (default + target locales)
+    pub expected_locales: &'a [String],
+    pub locales: &'a BTreeMap<String, LocaleFields>,
+    /// Screenshot count per locale; `None` when no manifest was downloaded for it
+    pub screenshots: &'a BTreeMap<String, Option<usize>>,
+    /// Translated fields whose source text changed since, per locale
+    pub stale: &'a BTreeMap<String, Vec<String>>,
+}
+
+/// Run every rule and return diagnostics sorted by locale, then severity
+pub fn validate(input: &ValidationInput) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for locale in input.expected_locales {
+        if !input.locales.contains_key(locale) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Error,
+                locale,
+                None,
+                "Locale is configured but missing from the local workspace".to_string(),
+            ));
+        }
+    }
+
+    for (locale, fields) in input.locales {
+        check_required_fields(locale, fields, &mut diagnostics);
+        check_limits(locale, fields, &mut diagnostics);
+        check_whitespace(locale, fields, &mut diagnostics);
+        check_keywords(locale, fields, &mut diagnostics);
+        check_screenshots(locale, input, &mut diagnostics);
+        check_stale(locale, input, &mut diagnostics);
+    }
+
+    diagnostics.sort_by(|a, b| {
+        (a.locale != input.default_locale, &a.locale, a.severity)
+            .cmp(&(b.locale != input.default_locale, &b.locale, b.severity))
+    });
+    diagnostics
+}
+
+fn non_empty<'a>(fields: &'a LocaleFields, field: &str) -> Option<&'a str> {
+    fields.get(field).map(|v| v.as_str()).filter(|v| !v.trim().is_empty())
+}
+
+fn check_required_fields(locale: &str, fields: &LocaleFields, diagnostics: &mut Vec<Diagnostic>) {
+    for field in REQUIRED_FIELDS {
+        if non_empty(fields, field).is_none() {
+            diagnostics.push(Diagnostic::new(Severity::Error, locale, Some(field), "Required field is empty".to_string()));
+        }
+    }
+
+    if non_empty(fields, "whatsNew").is_none() {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            locale,
+            Some("whatsNew"),
+            "What's New is empty; it is required for every update after the first release".to_string(),
+        ));
+    }
+}
+
+fn check_limits(locale: &str, fields: &LocaleFields, diagnostics: &mut Vec<Diagnostic>) {
+    for (field, limit) in FIELD_LIMITS {
+        if let Some(text) = non_empty(fields, field) {
+            let count = char_count(text);
+            if count > limit {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Error,
+                    locale,
+                    Some(field),
+                    format!("{} characters exceeds the limit of {}", count, limit),
+                ));
+            }
+        }
+    }
+
+    if let Some(description) = non_empty(fields, "description") {
+        if char_count(description) < 10 {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                locale,
+                Some("description"),
+                "Description is very short, consider adding more details".to_string(),
+            ));
+        }
+    }
+}
+
+fn check_whitespace(locale: &str, fields: &LocaleFields, diagnostics: &mut Vec<Diagnostic>) {
+    for field in ["name", "subtitle", "keywords"] {
+        if let Some(text) = non_empty(fields, field) {
+            if text.trim() != text {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Warning,
+                    locale,
+                    Some(field),
+                    "Leading or trailing whitespace".to_string(),
+                ));
+            }
+        }
+    }
+}
+
+fn check_keywords(locale: &str, fields: &LocaleFields, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(keywords) = non_empty(fields, "keywords") else {
+        return;
+    };
+
+    if keywords.contains(", ") {
+        diagnostics.push(Diagnostic::new(
+            Severity::Info,
+            locale,
+            Some("keywords"),
+            "Spaces after commas count against the 100 character limit".to_string(),
+        ));
+    }
+
+    let mut seen: Vec<String> = Vec::new();
+    let mut duplicates: Vec<String> = Vec::new();
+    for keyword in keywords.split(',').map(|k| k.trim().to_lowercase()).filter(|k| !k.is_empty()) {
+        if seen.contains(&keyword) {
+            if !duplicates.contains(&keyword) {
+                duplicates.push(keyword);
+            }
+        } else {
+            seen.push(keyword);
+        }
+    }
+    if !duplicates.is_empty() {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            locale,
+            Some("keywords"),
+            format!("Duplicate keywords: {}", duplicates.join(", ")),
+        ));
+    }
+
+    if let Some(name) = non_empty(fields, "name") {
+        let name = name.trim().to_lowercase();
+        if seen.contains(&name) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Info,
+                locale,
+                Some("keywords"),
+                "App name is already indexed; repeating it in keywords wastes characters".to_string(),
+            ));
+        }
+    }
+}
+
+fn check_screenshots(locale: &str, input: &ValidationInput, diagnostics: &mut Vec<Diagnostic>) {
+    let count = input.screenshots.get(locale).copied().flatten().unwrap_or(0);
+    if count > 0 {
+        return;
+    }
+
+    // Other locales fall back to the primary locale's screenshots
+    if locale == input.default_locale {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            locale,
+            Some("screenshots"),
+            "No screenshots for the primary locale".to_string(),
+        ));
+    } else {
+        diagnostics.push(Diagnostic::new(
+            Severity::Info,
+            locale,
+            Some("screenshots"),
+            format!("No localized screenshots; {} screenshots will be shown", input.default_locale),
+        ));
+    }
+}
+
+fn check_stale(locale: &str, input: &ValidationInput, diagnostics: &mut Vec<Diagnostic>) {
+    for field in input.stale.get(locale).into_iter().flatten() {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            locale,
+            Some(field),
+            format!(
+                "Translated from an older {} text; run 'translate --overwrite --locales {}' to refresh it or 'review' to keep it",
+                input.default_locale, locale
+            ),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance::{record_baseline, stale_fields, LocaleProvenance};
+
+    fn complete(name: &str) -> LocaleFields {
+        [
+            ("name", name),
+            ("description", "A clock widget for your home screen."),
+            ("keywords", "clock,widget,time"),
+            ("whatsNew", "Bug fixes."),
+        ]
+        .iter()
+        .map(|(field, value)| (field.to_string(), value.to_string()))
+        .collect()
+    }
+
+    fn run(locales: BTreeMap<String, LocaleFields>, expected: &[&str]) -> Vec<Diagnostic> {
+        let expected: Vec<String> = expected.iter().map(|l| l.to_string()).collect();
+        let screenshots = locales.keys().map(|locale| (locale.clone(), Some(3))).collect();
+        validate(&ValidationInput {
+            default_locale: "en-US",
+            expected_locales: &expected,
+            locales: &locales,
+            screenshots: &screenshots,
+            stale: &BTreeMap::new(),
+        })
+    }
+
+    fn findings(diagnostics: &[Diagnostic]) -> Vec<(Severity, &str, Option<&str>)> {
+        diagnostics.iter().map(|d| (d.severity, d.locale.as_str(), d.field.as_deref())).collect()
+    }
+
+    #[test]
+    fn complete_locales_pass() {
+        let locales = BTreeMap::from([("en-US".to_string(), complete("Hands Time"))]);
+        assert!(run(locales, &["en-US"]).is_empty());
+    }
+
+    #[test]
+    fn missing_locale_and_required_fields_are_errors() {
+        let mut fields = complete("Hands Time");
+        fields.insert("keywords".to_string(), "   ".to_string());
+        fields.remove("whatsNew");
+        let locales = BTreeMap::from([("en-US".to_string(), fields)]);
+
+        let diagnostics = run(locales, &["en-US", "ja"]);
+        assert_eq!(
+            findings(&diagnostics),
+            [
+                (Severity::Error, "en-US", Some("keywords")),
+                (Severity::Warning, "en-US", Some("whatsNew")),
+                (Severity::Error, "ja", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn limits_are_counted_in_graphemes() {
+        // 30 family emoji are 30 characters for App Store Connect, far more in bytes and chars
+        let mut fields = complete("Hands Time");
+        fields.insert("subtitle".to_string(), "👨‍👩‍👧".repeat(30));
+        let locales = BTreeMap::from([("en-US".to_string(), fields.clone())]);
+        assert!(run(locales, &["en-US"]).is_empty());
+
+        fields.insert("subtitle".to_string(), "👨‍👩‍👧".repeat(31));
+        let locales = BTreeMap::from([("en-US".to_string(), fields)]);
+        let diagnostics = run(locales, &["en-US"]);
+        assert_eq!(findings(&diagnostics), [(Severity::Error, "en-US", Some("subtitle"))]);
+        assert_eq!(diagnostics[0].message, "31 characters exceeds the limit of 30");
+    }
+
+    #[test]
+    fn keyword_duplicates_spacing_and_app_name() {
+        let mut fields = complete("Hands Time");
+        fields.insert("keywords".to_string(), "Clock, widget,clock,hands time,CLOCK".to_string());
+        let locales = BTreeMap::from([("en-US".to_string(), fields)]);
+
+        let messages: Vec<String> = run(locales, &["en-US"]).into_iter().map(|d| d.message).collect();
+        assert_eq!(
+            messages,
+            [
+                "Duplicate keywords: clock",
+                "Spaces after commas count against the 100 character limit",
+                "App name is already indexed; repeating it in keywords wastes characters",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_primary_locale_requires_screenshots() {
+        let locales: BTreeMap<String, LocaleFields> = BTreeMap::from([
+            ("en-US".to_string(), complete("Hands Time")),
+            ("ja".to_string(), complete("ハンズタイム")),
+        ]);
+        let screenshots = BTreeMap::from([("en-US".to_string(), None), ("ja".to_string(), Some(0))]);
+        let expected = vec!["en-US".to_string(), "ja".to_string()];
+
+        let diagnostics = validate(&ValidationInput {
+            default_locale: "en-US",
+            expected_locales: &expected,
+            locales: &locales,
+            screenshots: &screenshots,
+            stale: &BTreeMap::new(),
+        });
+        assert_eq!(
+            findings(&diagnostics),
+            [(Severity::Error, "en-US", Some("screenshots")), (Severity::Info, "ja", Some("screenshots"))]
+        );
+    }
+
+    #[test]
+    fn stale_pulled_translations_point_to_overwrite() {
+        // Pulled text is recorded as reviewed, which translate only replaces with --overwrite
+        let pulled = complete("Heure");
+        let mut provenance = LocaleProvenance::new();
+        record_baseline(&pulled, &mut provenance, &complete("Hands Time"), "en-US");
+        let stale = BTreeMap::from([("fr-FR".to_string(), stale_fields(&complete("Hands Time 2"), &provenance))]);
+
+        let locales = BTreeMap::from([("en-US".to_string(), complete("Hands Time 2")), ("fr-FR".to_string(), pulled)]);
+        let expected = vec!["en-US".to_string(), "fr-FR".to_string()];
+        let screenshots = locales.keys().map(|locale| (locale.clone(), Some(3))).collect();
+        let diagnostics = validate(&ValidationInput {
+            default_locale: "en-US",
+            expected_locales: &expected,
+            locales: &locales,
+            screenshots: &screenshots,
+            stale: &stale,
+        });
+
+        assert_eq!(findings(&diagnostics), [(Severity::Warning, "fr-FR", Some("name"))]);
+        assert_eq!(
+            diagnostics[0].message,
+            "Translated from an older en-US text; run 'translate --overwrite --locales fr-FR' to refresh it or 'review' to keep it"
+        );
+    }
+
+    #[test]
+    fn default_locale_sorts_first() {
+        let mut broken = complete("Hands Time");
+        broken.remove("name");
+        let locales = BTreeMap::from([("de-DE".to_string(), broken.clone()), ("en-US".to_string(), broken)]);
+
+        let diagnostics = run(locales, &[]);
+        let order: Vec<&str> = diagnostics.iter().map(|d| d.locale.as_str()).collect();
+        assert_eq!(order, ["en-US", "de-DE"]);
+    }
+}
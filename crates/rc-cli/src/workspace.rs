@@ -0,0 +1,367 @@
+use crate::config::Config;
+use crate::provenance::LocaleProvenance;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Localized text fields managed by rosetta-connect, in display order
+pub const METADATA_FIELDS: [&str; 6] = ["name", "subtitle", "promotionalText", "description", "keywords", "whatsNew"];
+
+/// Per-field files written into version snapshots (`<bundle_id>/<version>/<locale>/`) by the JS bridge,
+/// and into the working copy by `write_locale`
+pub const FIELD_FILES: [(&str, &str); 6] = [
+    ("name", "app-name.md"),
+    ("subtitle", "subtitle.md"),
+    ("promotionalText", "promotional-text.md"),
+    ("description", "description.md"),
+    ("keywords", "keywords.md"),
+    ("whatsNew", "whats-new.md"),
+];
+
+/// Text fields of one locale, keyed by App Store Connect attribute name
+pub type LocaleFields = BTreeMap<String, String>;
+
+/// One display type's screenshots from a `screenshots.json` manifest
+#[derive(Debug, Clone)]
+pub struct ScreenshotSet {
+    pub display_type: String,
+    pub items: Vec<ScreenshotFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScreenshotFile {
+    pub path: PathBuf,
+    /// Width × height in pixels; `None` when the file is missing or not a readable image
+    pub dimensions: Option<(usize, usize)>,
+}
+
+/// The local copy of an app's listing, as written by `pull`:
+/// `<bundle_id>/current/<locale>/metadata.json` plus `<bundle_id>/current/summary.json`
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    /// Open the workspace for the configured app in the current directory
+    pub fn open(config: &Config) -> Result<Self> {
+        let mut root = std::env::current_dir()
+            .context("Failed to get current directory")?;
+        root.push(&config.app.bundle_id);
+        Ok(Self { root })
+    }
+
+    /// Workspace rooted at `root` rather than under the current directory
+    #[cfg(test)]
+    pub fn at(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Directory holding the working copy of every locale
+    pub fn current_dir(&self) -> PathBuf {
+        self.root.join("current")
+    }
+
+    pub fn locale_dir(&self, locale: &str) -> PathBuf {
+        self.current_dir().join(locale)
+    }
+
+    /// Locales that have a `metadata.json` in the working copy, sorted
+    pub fn locales(&self) -> Result<Vec<String>> {
+        let current_dir = self.current_dir();
+        if !current_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut locales = Vec::new();
+        for entry in fs::read_dir(&current_dir)
+            .with_context(|| format!("Failed to read workspace directory: {}", current_dir.display()))?
+        {
+            let entry = entry.context("Failed to read workspace entry")?;
+            if entry.path().join("metadata.json").is_file() {
+                locales.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        locales.sort();
+        Ok(locales)
+    }
+
+    /// Read the text fields of one locale
+    pub fn read_locale(&self, locale: &str) -> Result<LocaleFields> {
+        let metadata_file = self.locale_dir(locale).join("metadata.json");
+        let content = fs::read_to_string(&metadata_file)
+            .with_context(|| format!("Failed to read metadata for locale {}", locale))?;
+        let value: Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", metadata_file.display()))?;
+        Ok(fields_from_value(&value))
+    }
+
+    /// Read the text fields of every locale in the working copy
+    pub fn read_all(&self) -> Result<BTreeMap<String, LocaleFields>> {
+        let mut all = BTreeMap::new();
+        for locale in self.locales()? {
+            let fields = self.read_locale(&locale)?;
+            all.insert(locale, fields);
+        }
+        Ok(all)
+    }
+
+    /// Overwrite the text fields of one locale in the working copy: `metadata.json`
+    /// plus one `.md` file per non-empty field for reviewing in an editor
+    pub fn write_locale(&self, locale: &str, fields: &LocaleFields) -> Result<()> {
+        let locale_dir = self.locale_dir(locale);
+        fs::create_dir_all(&locale_dir)
+            .with_context(|| format!("Failed to create locale directory for {}", locale))?;
+        let content = serde_json::to_string_pretty(fields)
+            .context("Failed to serialize locale metadata")?;
+        fs::write(locale_dir.join("metadata.json"), content)
+            .with_context(|| format!("Failed to write metadata for locale {}", locale))?;
+
+        for (field, file_name) in FIELD_FILES {
+            let path = locale_dir.join(file_name);
+            match fields.get(field).filter(|text| !text.trim().is_empty()) {
+                Some(text) => fs::write(&path, text)
+                    .with_context(|| format!("Failed to write {}", path.display()))?,
+                None if path.exists() => fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Provenance of a locale's machine-translated fields; empty when nothing was translated
+    pub fn read_provenance(&self, locale: &str) -> Result<LocaleProvenance> {
+        let provenance_file = self.locale_dir(locale).join("provenance.json");
+        if !provenance_file.exists() {
+            return Ok(LocaleProvenance::new());
+        }
+        let content = fs::read_to_string(&provenance_file)
+            .with_context(|| format!("Failed to read {}", provenance_file.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", provenance_file.display()))
+    }
+
+    pub fn write_provenance(&self, locale: &str, provenance: &LocaleProvenance) -> Result<()> {
+        let locale_dir = self.locale_dir(locale);
+        fs::create_dir_all(&locale_dir)
+            .with_context(|| format!("Failed to create locale directory for {}", locale))?;
+        let content = serde_json::to_string_pretty(provenance)
+            .context("Failed to serialize translation provenance")?;
+        fs::write(locale_dir.join("provenance.json"), content)
+            .with_context(|| format!("Failed to write translation provenance for locale {}", locale))
+    }
+
+    /// Directory of a downloaded version snapshot (`<bundle_id>/<version>`)
+    pub fn version_dir(&self, version: &str) -> PathBuf {
+        self.root.join(version)
+    }
+
+    /// Read every locale of a version snapshot from its per-field `.md` files
+    pub fn read_version(&self, version: &str) -> Result<BTreeMap<String, LocaleFields>> {
+        let version_dir = self.version_dir(version);
+        if !version_dir.is_dir() {
+            anyhow::bail!(
+                "No snapshot for version {} at {}. Pull that version first.",
+                version,
+                version_dir.display()
+            );
+        }
+
+        let mut all = BTreeMap::new();
+        for entry in fs::read_dir(&version_dir)
+            .with_context(|| format!("Failed to read version directory: {}", version_dir.display()))?
+        {
+            let entry = entry.context("Failed to read version entry")?;
+            if !entry.path().is_dir() {
+                continue;
+            }
+
+            let mut fields = LocaleFields::new();
+            for (field, file_name) in FIELD_FILES {
+                let path = entry.path().join(file_name);
+                if path.is_file() {
+                    let text = fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?;
+                    fields.insert(field.to_string(), text);
+                }
+            }
+            all.insert(entry.file_name().to_string_lossy().to_string(), fields);
+        }
+        Ok(all)
+    }
+
+    /// Screenshot manifest (`screenshots.json`) of a locale in a version snapshot
+    pub fn read_screenshot_manifest(&self, version: &str, locale: &str) -> Result<Option<Value>> {
+        let manifest_file = self.version_dir(version).join(locale).join("screenshots.json");
+        if !manifest_file.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&manifest_file)
+            .with_context(|| format!("Failed to read {}", manifest_file.display()))?;
+        let manifest = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", manifest_file.display()))?;
+        Ok(Some(manifest))
+    }
+
+    pub fn write_screenshot_manifest(&self, version: &str, locale: &str, manifest: &Value) -> Result<()> {
+        let locale_dir = self.version_dir(version).join(locale);
+        fs::create_dir_all(&locale_dir)
+            .with_context(|| format!("Failed to create {}", locale_dir.display()))?;
+        let content = serde_json::to_string_pretty(manifest)
+            .context("Failed to serialize screenshot manifest")?;
+        fs::write(locale_dir.join("screenshots.json"), content)
+            .with_context(|| format!("Failed to write screenshot manifest for locale {}", locale))
+    }
+
+    /// Screenshots of a locale in a version snapshot, per display type, with their pixel dimensions
+    pub fn screenshots(&self, version: &str, locale: &str) -> Result<Vec<ScreenshotSet>> {
+        let Some(manifest) = self.read_screenshot_manifest(version, locale)? else {
+            return Ok(Vec::new());
+        };
+
+        let locale_dir = self.version_dir(version).join(locale);
+        let sets = manifest.get("sets").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+
+        Ok(sets
+            .iter()
+            .map(|set| {
+                let items = set.get("items").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+                ScreenshotSet {
+                    display_type: set.get("displayType").and_then(|v| v.as_str()).unwrap_or("UNKNOWN").to_string(),
+                    items: items
+                        .iter()
+                        .filter_map(|item| item.get("file").and_then(|v| v.as_str()))
+                        .map(|file| {
+                            let path = locale_dir.join(file);
+                            // Only the image header is read
+                            let dimensions = imagesize::size(&path).ok().map(|size| (size.width, size.height));
+                            ScreenshotFile { path, dimensions }
+                        })
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
+    /// Number of screenshots listed in a locale's manifest, `None` if there is no manifest
+    pub fn screenshot_count(&self, version: &str, locale: &str) -> Result<Option<usize>> {
+        Ok(self.read_screenshot_manifest(version, locale)?.map(|manifest| {
+            manifest
+                .get("sets")
+                .and_then(|v| v.as_array())
+                .map(|sets| {
+                    sets.iter()
+                        .filter_map(|set| set.get("items").and_then(|v| v.as_array()))
+                        .map(|items| items.len())
+                        .sum()
+                })
+                .unwrap_or(0)
+        }))
+    }
+
+    /// The full pull result saved alongside the locales, if any
+    pub fn read_summary(&self) -> Result<Option<Value>> {
+        let summary_file = self.current_dir().join("summary.json");
+        if !summary_file.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&summary_file)
+            .context("Failed to read workspace summary")?;
+        let summary = serde_json::from_str(&content)
+            .context("Failed to parse workspace summary")?;
+        Ok(Some(summary))
+    }
+
+    pub fn write_summary(&self, summary: &Value) -> Result<()> {
+        fs::create_dir_all(self.current_dir())
+            .context("Failed to create workspace directory")?;
+        let content = serde_json::to_string_pretty(summary)
+            .context("Failed to serialize workspace summary")?;
+        fs::write(self.current_dir().join("summary.json"), content)
+            .context("Failed to write workspace summary")
+    }
+
+    /// App Store version the working copy was pulled from (`summary.json`)
+    pub fn pulled_version(&self) -> Result<Option<String>> {
+        Ok(self.read_summary()?
+            .and_then(|s| s.get("appVersion").and_then(|v| v.as_str()).map(|v| v.to_string())))
+    }
+}
+
+/// Extract the known text fields from a locale's metadata object
+pub fn fields_from_value(value: &Value) -> LocaleFields {
+    let mut fields = LocaleFields::new();
+    if let Some(obj) = value.as_object() {
+        for field in METADATA_FIELDS {
+            if let Some(text) = obj.get(field).and_then(|v| v.as_str()) {
+                fields.insert(field.to_string(), text.to_string());
+            }
+        }
+    }
+    fields
+}
+
+/// Stable SHA-256 hex digest of a piece of text
+pub fn content_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Stable hash of a locale's non-empty fields, used to detect remote drift
+pub fn locale_hash(fields: &LocaleFields) -> String {
+    let canonical: LocaleFields = fields
+        .iter()
+        .filter(|(_, value)| !value.trim().is_empty())
+        .map(|(field, value)| (field.clone(), value.clone()))
+        .collect();
+    content_hash(&serde_json::to_string(&canonical).unwrap_or_default())
+}
+
+/// Split a full `asc_download` result into per-locale fields
+pub fn locales_from_download(data: &Value) -> BTreeMap<String, LocaleFields> {
+    data.get("metadata")
+        .and_then(|m| m.as_object())
+        .map(|metadata| {
+            metadata
+                .iter()
+                .map(|(locale, value)| (locale.clone(), fields_from_value(value)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Download the App Store Connect localizations of the configured app, for `version` or else the
+/// latest live version
+pub async fn fetch_remote(config: &Config, version: Option<&str>) -> Result<BTreeMap<String, LocaleFields>> {
+    let data = rc_node::asc_download(config.app.bundle_id.clone(), version.map(str::to_string)).await
+        .context("Failed to download remote state")?;
+    Ok(locales_from_download(&data))
+}
+
+/// Source-locale text to translate: the local working copy, or App Store Connect with `from_remote`
+pub async fn read_source(config: &Config, from_remote: bool) -> Result<LocaleFields> {
+    let source_locale = &config.app.default_locale;
+
+    if from_remote {
+        println!("📥 Fetching {} source text from App Store Connect...", source_locale);
+        rc_node::init_node_runtime()
+            .context("Failed to initialize Node.js runtime")?;
+        return fetch_remote(config, None)
+            .await?
+            .remove(source_locale)
+            .with_context(|| format!("No {} metadata found on App Store Connect", source_locale));
+    }
+
+    let workspace = Workspace::open(config)?;
+    if !workspace.locales()?.contains(source_locale) {
+        anyhow::bail!(
+            "No local {} metadata in {}. Run 'rosetta-connect pull' first, or use --from-remote.",
+            source_locale,
+            workspace.current_dir().display()
+        );
+    }
+    println!("📂 Reading {} source text from the local workspace...", source_locale);
+    workspace.read_locale(source_locale)
+}
@@ -0,0 +1,208 @@
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+
+const FIXTURES: &str = r#"{
+  "sourceLocale": "en-US",
+  "glossary": {
+    "doNotTranslate": ["Habitly"],
+    "terms": { "streak": { "de-DE": "Serie" } }
+  },
+  "cases": [
+    {
+      "field": "subtitle",
+      "source": "Keep your Habitly streak",
+      "references": { "de-DE": "Halte deine Habitly-Serie" }
+    }
+  ]
+}"#;
+
+/// The evaluated template: the stub answers prompts that ask for brevity with the reference
+const BRIEF_TEMPLATE: &str = "Translate this {{field}} into {{target_language}}. Be brief.\n{{glossary}}\n\n\"{{text}}\"\n\nRespond with the translation only.\n";
+
+/// OpenAI-compatible chat completions stub: a long answer that drops the glossary terms for any
+/// prompt, except the reference translation for prompts that say "Be brief"
+fn start_stub() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind stub server");
+    let address = listener.local_addr().expect("stub address");
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            respond(stream);
+        }
+    });
+
+    format!("http://{}/v1", address)
+}
+
+fn respond(mut stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).expect("read request body");
+
+    let request: Value = serde_json::from_slice(&body).expect("parse request");
+    let prompt = request["messages"][0]["content"].as_str().unwrap_or_default();
+    let answer = if prompt.contains("Be brief") {
+        "Halte deine Habitly-Serie"
+    } else {
+        "Bleib jeden Tag bei deinen Gewohnheiten und verliere nie den Faden"
+    };
+
+    let response = serde_json::json!({
+        "choices": [{ "message": { "role": "assistant", "content": answer } }],
+        "usage": { "prompt_tokens": prompt.len() / 4, "completion_tokens": answer.len() / 4 },
+    })
+    .to_string();
+    let _ = write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.len(),
+        response
+    );
+}
+
+/// A project in a fresh temp directory, with fixtures and the AI pointed at `base_url`
+fn project(name: &str, base_url: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rosetta-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".rosetta")).expect("create project");
+
+    fs::write(
+        dir.join("rosetta.toml"),
+        format!(
+            "[app]\nbundle_id = \"com.example.habitly\"\ndefault_locale = \"en-US\"\ntarget_locales = [\"de-DE\"]\n\n\
+             [ai]\nprovider = \"openai-compatible\"\nmodel = \"stub-model\"\ntemperature = 0.2\nmax_tokens = 256\nbase_url = \"{}\"\napi_key_env = \"ROSETTA_STUB_KEY\"\n\n\
+             [ai.pricing.\"stub-model\"]\ninput = 1.0\noutput = 2.0\n",
+            base_url
+        ),
+    )
+    .expect("write rosetta.toml");
+    fs::write(dir.join(".rosetta").join("eval.json"), FIXTURES).expect("write fixtures");
+    fs::write(dir.join("brief.txt"), BRIEF_TEMPLATE).expect("write template");
+
+    dir
+}
+
+fn rosetta(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rosetta-connect"))
+        .args(args)
+        .current_dir(dir)
+        .env("ROSETTA_STUB_KEY", "test")
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("run rosetta-connect");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(
+        output.status.success(),
+        "rosetta-connect {} failed:\n{}\n{}",
+        args.join(" "),
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout
+}
+
+/// Run a command that is expected to fail and return what it printed to stderr
+fn rosetta_fails(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rosetta-connect"))
+        .args(args)
+        .current_dir(dir)
+        .env("ROSETTA_STUB_KEY", "test")
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("run rosetta-connect");
+    assert!(
+        !output.status.success(),
+        "rosetta-connect {} succeeded:\n{}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stdout)
+    );
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+/// Row of the comparison table for one version, with the cells split out
+fn row<'a>(table: &'a str, version: &str) -> Vec<&'a str> {
+    let line = table
+        .lines()
+        .find(|line| line.trim_start_matches('│').trim_start().starts_with(version))
+        .unwrap_or_else(|| panic!("no '{}' row in:\n{}", version, table));
+    line.split(['│', '┆']).map(str::trim).filter(|cell| !cell.is_empty()).collect()
+}
+
+#[test]
+fn eval_compares_template_with_the_version_it_replaced() {
+    let dir = project("eval-history", &start_stub());
+    rosetta(&dir, &["template", "create", "default", "--file", "brief.txt"]);
+
+    let output = rosetta(&dir, &["template", "eval", "default"]);
+    assert!(output.contains("1 case(s) from .rosetta/eval.json"), "{}", output);
+
+    // The project template overrides the built-in default, so that is the previous version
+    let before = row(&output, "built-in");
+    assert_eq!(&before[1..4], ["0/1 (0%)", "0/1 (0%)", "0.24"], "{}", output);
+    let after = row(&output, "current");
+    assert_eq!(&after[1..4], ["1/1 (100%)", "1/1 (100%)", "1.00"], "{}", output);
+    let change = row(&output, "Change");
+    assert_eq!(&change[1..4], ["+100 pts", "+100 pts", "+0.76"], "{}", output);
+
+    fs::remove_dir_all(&dir).expect("remove project");
+}
+
+#[test]
+fn eval_of_a_template_without_history_shows_its_scores_only() {
+    let dir = project("eval-no-history", &start_stub());
+    fs::create_dir_all(dir.join(".rosetta").join("templates")).expect("create templates");
+    fs::write(dir.join(".rosetta").join("templates").join("brief.txt"), BRIEF_TEMPLATE).expect("write template");
+
+    let output = rosetta(&dir, &["template", "eval", "brief", "--locales", "de-DE"]);
+    assert!(output.contains("'brief' has no previous version"), "{}", output);
+    assert_eq!(&row(&output, "current")[1..4], ["1/1 (100%)", "1/1 (100%)", "1.00"], "{}", output);
+    assert!(!output.contains("Change"), "{}", output);
+
+    fs::remove_dir_all(&dir).expect("remove project");
+}
+
+#[test]
+fn eval_spend_is_recorded_in_the_cost_ledger() {
+    let dir = project("eval-ledger", &start_stub());
+    rosetta(&dir, &["template", "create", "default", "--file", "brief.txt"]);
+    rosetta(&dir, &["template", "eval", "default"]);
+
+    let report: Value = serde_json::from_str(&rosetta(&dir, &["cost", "report", "--by", "app", "--format", "json"]))
+        .expect("parse cost report");
+    assert_eq!(report[0]["key"], "com.example.habitly", "{}", report);
+    assert_eq!(report[0]["runs"], 1, "{}", report);
+    assert!(report[0]["cost"].as_f64().unwrap_or_default() > 0.0, "{}", report);
+
+    fs::remove_dir_all(&dir).expect("remove project");
+}
+
+#[test]
+fn eval_over_the_spending_limit_is_refused() {
+    let dir = project("eval-budget", &start_stub());
+    let config = fs::read_to_string(dir.join("rosetta.toml")).expect("read rosetta.toml");
+    fs::write(dir.join("rosetta.toml"), config.replace("[ai]\n", "[ai]\nmax_cost_per_run = 0.000001\n")).expect("write rosetta.toml");
+    rosetta(&dir, &["template", "create", "default", "--file", "brief.txt"]);
+
+    let error = rosetta_fails(&dir, &["template", "eval", "default"]);
+    assert!(error.contains("is over max_cost_per_run"), "{}", error);
+    assert!(!dir.join(".rosetta").join("ledger.jsonl").exists());
+
+    fs::remove_dir_all(&dir).expect("remove project");
+}
@@ -9,8 +9,9 @@ pub async fn asc_upload(meta: Value) -> Result<Value> {
     call_node_function("asc_upload", meta).await
 }
 
-pub async fn asc_download(app_id: String) -> Result<Value> {
-    call_node_function("asc_download", serde_json::json!(app_id)).await
+/// Download metadata of the latest live version, or of `version` when given
+pub async fn asc_download(app_id: String, version: Option<String>) -> Result<Value> {
+    call_node_function("asc_download", serde_json::json!({ "appId": app_id, "version": version })).await
 }
 
 pub async fn asc_validate(content: Value) -> Result<Value> {
@@ -36,7 +37,7 @@ async fn call_node_function(function_name: &str, args: Value) -> Result<Value> {
     let js_file = js_dir.join("dist").join("asc.js");
     if !js_file.exists() {
         // Try to build TypeScript first
-        println!("Building TypeScript...");
+        eprintln!("Building TypeScript...");
         let build_output = Command::new("npm")
             .args(["run", "build"])
             .current_dir(&js_dir)
@@ -83,11 +84,8 @@ main();
         .context("Failed to spawn Node.js process")?;
 
     // Stream stderr lines in real-time for progress/debug logs
-    let mut stderr_buf = String::new();
-    let mut stdout_buf: Vec<u8> = Vec::new();
-
     let mut stderr_reader = child.stderr.take().map(BufReader::new);
-    let mut stdout_reader = child.stdout.take();
+    let stdout_reader = child.stdout.take();
 
     let stderr_task = tokio::spawn(async move {
         if let Some(ref mut s) = stderr_reader {
@@ -124,7 +122,7 @@ main();
 
     // Ensure readers finished
     let _ = stderr_task.await;
-    stdout_buf = stdout_task
+    let stdout_buf = stdout_task
         .await
         .unwrap_or_default();
 
@@ -156,10 +154,10 @@ main();
 
 // Initialize the Node.js runtime
 pub fn init_node_runtime() -> Result<()> {
-    println!("Initializing Node.js runtime...");
+    eprintln!("Initializing Node.js runtime...");
     
     // Check if Node.js is available
-    if let Err(_) = which::which("node") {
+    if which::which("node").is_err() {
         return Err(anyhow::anyhow!("Node.js not found. Please install Node.js to use this feature."));
     }
     
@@ -1,5 +1,5 @@
 import { AppStoreConnectAPI } from 'appstore-connect-sdk';
-import { AppsApi, AppInfosApi, AppStoreVersionsApi, AppStoreVersionLocalizationsApi, AppScreenshotSetsApi, AppScreenshotsApi } from 'appstore-connect-sdk/openapi';
+import { AppsApi, AppInfosApi, AppInfoLocalizationsApi, AppStoreVersionsApi, AppStoreVersionLocalizationsApi, AppScreenshotSetsApi, AppScreenshotsApi } from 'appstore-connect-sdk/openapi';
 import * as fs from 'fs';
 import * as path from 'path';
 import * as https from 'https';
@@ -45,22 +45,35 @@ function loadEnvFile() {
 
 interface AppMetadata {
   appId: string;
+  version?: string;
   locale: string;
   name?: string;
   subtitle?: string;
   description?: string;
   keywords?: string;
   whatsNew?: string;
+  promotionalText?: string;
   screenshots?: string[];
 }
 
+interface FieldUploadResult {
+  field: string;
+  success: boolean;
+  error?: string;
+}
+
 interface UploadResult {
   success: boolean;
   uploadedFiles: number;
   message: string;
   errors?: string[];
+  fields?: FieldUploadResult[];
 }
 
+// Fields stored on the app info localization; everything else lives on the version localization
+const APP_INFO_FIELDS = ['name', 'subtitle'];
+const VERSION_FIELDS = ['description', 'keywords', 'whatsNew', 'promotionalText'];
+
 interface DownloadResult {
   appId: string;
   locales: string[];
@@ -73,6 +86,7 @@ class AppStoreConnectWrapper {
   private client: AppStoreConnectAPI;
   private appsApi: AppsApi | null = null;
   private appInfosApi: AppInfosApi | null = null;
+  private appInfoLocalizationsApi: AppInfoLocalizationsApi | null = null;
   private appStoreVersionsApi: AppStoreVersionsApi | null = null;
   private appStoreVersionLocalizationsApi: AppStoreVersionLocalizationsApi | null = null;
   private appScreenshotSetsApi: AppScreenshotSetsApi | null = null;
@@ -146,6 +160,9 @@ class AppStoreConnectWrapper {
     if (!this.appInfosApi) {
       this.appInfosApi = await this.client.create(AppInfosApi);
     }
+    if (!this.appInfoLocalizationsApi) {
+      this.appInfoLocalizationsApi = await this.client.create(AppInfoLocalizationsApi);
+    }
     if (!this.appStoreVersionsApi) {
       this.appStoreVersionsApi = await this.client.create(AppStoreVersionsApi);
     }
@@ -161,33 +178,178 @@ class AppStoreConnectWrapper {
   }
   
   async uploadMetadata(metadata: AppMetadata): Promise<UploadResult> {
-    try {
-      console.log(`Uploading metadata for app ${metadata.appId}, locale ${metadata.locale}`);
-      
-      // TODO: Implement actual App Store Connect API calls
-      // This is a placeholder implementation
-      
-      // Simulate API calls
-      await this.delay(1000);
-      
+    const fieldNames = [...APP_INFO_FIELDS, ...VERSION_FIELDS]
+      .filter(field => typeof (metadata as any)[field] === 'string');
+    console.error(`⬆️  [${metadata.locale}] Uploading ${fieldNames.length} field(s) for ${metadata.appId} ${metadata.version || ''}`);
+
+    if (!this.client) {
+      console.error('⚠️  No App Store Connect credentials configured, simulating upload');
       return {
         success: true,
-        uploadedFiles: 1,
-        message: `Successfully uploaded metadata for ${metadata.locale}`,
+        uploadedFiles: fieldNames.length,
+        message: `Simulated upload of ${fieldNames.length} field(s) for ${metadata.locale} (mock mode)`,
+        fields: fieldNames.map(field => ({ field, success: true })),
       };
+    }
+
+    const fields: FieldUploadResult[] = [];
+    try {
+      await this.initializeApis();
+
+      const appsResponse = await this.appsApi!.appsGetCollection({
+        filterBundleId: [metadata.appId],
+        limit: 1,
+      });
+      if (!appsResponse.data || appsResponse.data.length === 0) {
+        throw new Error(`App with bundle ID ${metadata.appId} not found`);
+      }
+      const app = appsResponse.data[0];
+
+      const versionFields = VERSION_FIELDS.filter(field => fieldNames.includes(field));
+      if (versionFields.length > 0) {
+        fields.push(...await this.uploadVersionFields(app.id, metadata, versionFields));
+      }
+
+      const appInfoFields = APP_INFO_FIELDS.filter(field => fieldNames.includes(field));
+      if (appInfoFields.length > 0) {
+        fields.push(...await this.uploadAppInfoFields(app.id, metadata, appInfoFields));
+      }
     } catch (error) {
-      return {
+      // Anything not yet attempted failed with the same root cause
+      const message = error instanceof Error ? error.message : 'Unknown error';
+      for (const field of fieldNames) {
+        if (!fields.some(f => f.field === field)) {
+          fields.push({ field, success: false, error: message });
+        }
+      }
+    }
+
+    const failed = fields.filter(f => !f.success);
+    const succeeded = fields.length - failed.length;
+    return {
+      success: failed.length === 0,
+      uploadedFiles: succeeded,
+      message: failed.length === 0
+        ? `Successfully uploaded ${succeeded} field(s) for ${metadata.locale}`
+        : `Uploaded ${succeeded}/${fields.length} field(s) for ${metadata.locale}`,
+      errors: failed.length > 0 ? failed.map(f => `${f.field}: ${f.error}`) : undefined,
+      fields,
+    };
+  }
+
+  // Update description/keywords/whatsNew/promotionalText on the version localization, one field per request
+  private async uploadVersionFields(appId: string, metadata: AppMetadata, fieldNames: string[]): Promise<FieldUploadResult[]> {
+    if (!metadata.version) {
+      throw new Error('Version string is required to upload version localizations');
+    }
+
+    const versionsResponse: any = await this.appsApi!.appsAppStoreVersionsGetToManyRelated({
+      id: appId,
+      filterVersionString: [metadata.version],
+      limit: 1,
+    } as any);
+    const version = versionsResponse?.data?.[0];
+    if (!version) {
+      throw new Error(`Version ${metadata.version} not found in App Store Connect`);
+    }
+
+    const localizationsResponse: any = await this.appStoreVersionsApi!.appStoreVersionsAppStoreVersionLocalizationsGetToManyRelated({
+      id: version.id,
+      limit: 50,
+    });
+    let localization = (localizationsResponse?.data || []).find((l: any) => l.attributes?.locale === metadata.locale);
+
+    if (!localization) {
+      console.error(`➕ [${metadata.locale}] Creating version localization`);
+      const created: any = await this.appStoreVersionLocalizationsApi!.appStoreVersionLocalizationsCreateInstance({
+        appStoreVersionLocalizationCreateRequest: {
+          data: {
+            type: 'appStoreVersionLocalizations',
+            attributes: { locale: metadata.locale },
+            relationships: { appStoreVersion: { data: { type: 'appStoreVersions', id: version.id } } },
+          },
+        },
+      } as any);
+      localization = created?.data;
+    }
+
+    const results: FieldUploadResult[] = [];
+    for (const field of fieldNames) {
+      try {
+        await this.appStoreVersionLocalizationsApi!.appStoreVersionLocalizationsUpdateInstance({
+          id: localization.id,
+          appStoreVersionLocalizationUpdateRequest: {
+            data: {
+              type: 'appStoreVersionLocalizations',
+              id: localization.id,
+              attributes: { [field]: (metadata as any)[field] },
+            },
+          },
+        } as any);
+        results.push({ field, success: true });
+      } catch (error) {
+        results.push({ field, success: false, error: this.describeApiError(error) });
+      }
+    }
+    return results;
+  }
+
+  // Update name/subtitle on the editable app info localization
+  private async uploadAppInfoFields(appId: string, metadata: AppMetadata, fieldNames: string[]): Promise<FieldUploadResult[]> {
+    const appInfosResponse: any = await this.appsApi!.appsAppInfosGetToManyRelated({ id: appId, limit: 10 });
+    const appInfos: any[] = appInfosResponse?.data || [];
+    // Live apps have two app infos; only the one that is not READY_FOR_SALE accepts edits
+    const appInfo = appInfos.find(info => info.attributes?.appStoreState !== 'READY_FOR_SALE') || appInfos[0];
+    if (!appInfo) {
+      throw new Error('No App Info found for app');
+    }
+
+    const localizationsResponse: any = await this.appInfosApi!.appInfosAppInfoLocalizationsGetToManyRelated({
+      id: appInfo.id,
+      limit: 50,
+    });
+    const localization = (localizationsResponse?.data || []).find((l: any) => l.attributes?.locale === metadata.locale);
+    if (!localization) {
+      return fieldNames.map(field => ({
+        field,
         success: false,
-        uploadedFiles: 0,
-        message: 'Upload failed',
-        errors: [error instanceof Error ? error.message : 'Unknown error'],
-      };
+        error: `No App Info localization for ${metadata.locale}; add the language in App Store Connect first`,
+      }));
     }
+
+    const results: FieldUploadResult[] = [];
+    for (const field of fieldNames) {
+      try {
+        await this.appInfoLocalizationsApi!.appInfoLocalizationsUpdateInstance({
+          id: localization.id,
+          appInfoLocalizationUpdateRequest: {
+            data: {
+              type: 'appInfoLocalizations',
+              id: localization.id,
+              attributes: { [field]: (metadata as any)[field] },
+            },
+          },
+        } as any);
+        results.push({ field, success: true });
+      } catch (error) {
+        results.push({ field, success: false, error: this.describeApiError(error) });
+      }
+    }
+    return results;
+  }
+
+  // App Store Connect returns a JSON body with `errors[].detail`; surface that instead of "Response returned an error code"
+  private describeApiError(error: any): string {
+    const details = error?.response?.data?.errors || error?.errors;
+    if (Array.isArray(details) && details.length > 0) {
+      return details.map((e: any) => e.detail || e.title || e.code).join('; ');
+    }
+    return error instanceof Error ? error.message : 'Unknown error';
   }
   
   async uploadScreenshots(appId: string, locale: string, screenshotPaths: string[]): Promise<UploadResult> {
     try {
-      console.log(`Uploading ${screenshotPaths.length} screenshots for app ${appId}, locale ${locale}`);
+      console.error(`Uploading ${screenshotPaths.length} screenshots for app ${appId}, locale ${locale}`);
       
       // TODO: Implement actual screenshot upload
       // This involves:
@@ -212,8 +374,9 @@ class AppStoreConnectWrapper {
     }
   }
   
-  async downloadAppInfo(appId: string): Promise<DownloadResult> {
-    console.error(`🔄 Downloading app info for ${appId}`);
+  // Download metadata of the latest live version, or of `version` when given
+  async downloadAppInfo(appId: string, version?: string): Promise<DownloadResult> {
+    console.error(`🔄 Downloading app info for ${appId}${version ? ` (version ${version})` : ''}`);
     
     // If no valid client, use mock data immediately
     if (!this.client) {
@@ -301,11 +464,13 @@ class AppStoreConnectWrapper {
         // Step 2: Get App Store Versions and their Localizations  
         console.error('📦 Step 2: Fetching App Store Version Localizations...');
         
-        const appStoreVersionsResponse = await this.appsApi!.appsAppStoreVersionsGetToManyRelated({
-          id: app.id,
-          filterAppStoreState: ['READY_FOR_SALE', 'PROCESSING_FOR_APP_STORE', 'PENDING_APPLE_RELEASE'],
-          limit: 5
-        });
+        const appStoreVersionsResponse = await this.appsApi!.appsAppStoreVersionsGetToManyRelated(version
+          ? ({ id: app.id, filterVersionString: [version], limit: 1 } as any)
+          : {
+              id: app.id,
+              filterAppStoreState: ['READY_FOR_SALE', 'PROCESSING_FOR_APP_STORE', 'PENDING_APPLE_RELEASE'],
+              limit: 5
+            });
         
         if (appStoreVersionsResponse.data && appStoreVersionsResponse.data.length > 0) {
           const latestVersion = appStoreVersionsResponse.data[0];
@@ -337,6 +502,7 @@ class AppStoreConnectWrapper {
                   metadata[locale].description = versionLocalization.attributes?.description || '';
                   metadata[locale].keywords = versionLocalization.attributes?.keywords || '';
                   metadata[locale].whatsNew = versionLocalization.attributes?.whatsNew || '';
+                  metadata[locale].promotionalText = versionLocalization.attributes?.promotionalText || '';
 
                   if (vLocId) {
                     versionLocalizationIdsByLocale[locale] = vLocId;
@@ -373,7 +539,9 @@ class AppStoreConnectWrapper {
           }
           
         } else {
-          console.error('⚠️  No App Store Versions found in expected states');
+          console.error(version
+            ? `⚠️  Version ${version} not found in App Store Connect`
+            : '⚠️  No App Store Versions found in expected states');
         }
         
         // Step 3: Fill in missing data or use fallbacks
@@ -781,6 +949,10 @@ class AppStoreConnectWrapper {
         if (localeData.subtitle) {
           fs.writeFileSync(path.join(cacheDir, 'subtitle.md'), localeData.subtitle);
         }
+
+        if (localeData.promotionalText) {
+          fs.writeFileSync(path.join(cacheDir, 'promotional-text.md'), localeData.promotionalText);
+        }
         
         // Save complete metadata as JSON
         const metadataJson = {
@@ -846,12 +1018,15 @@ export async function asc_upload(metadata: any): Promise<any> {
   }
 }
 
-export async function asc_download(appId: string): Promise<any> {
+export async function asc_download(args: string | { appId: string; version?: string | null }): Promise<any> {
   if (!ascWrapper) {
     await initializeASC();
   }
   
-  return await ascWrapper.downloadAppInfo(appId);
+  if (typeof args === 'string') {
+    return await ascWrapper.downloadAppInfo(args);
+  }
+  return await ascWrapper.downloadAppInfo(args.appId, args.version || undefined);
 }
 
 export async function asc_validate(content: any): Promise<any> {
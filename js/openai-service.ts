@@ -6,6 +6,25 @@ interface TranslationRequest {
   targetLocale: string;
   context?: string;
   field?: string;
+  localeNames?: Record<string, string>;
+  /** Approved translations of similar text from the translation memory */
+  references?: MemoryReference[];
+  /** Glossary terms to keep exactly as written */
+  doNotTranslate?: string[];
+  /** Fixed renderings of glossary terms in the target locale */
+  terms?: Record<string, string>;
+  /** Style guide instructions for the target locale */
+  style?: string[];
+  /** Character limit a previous translation exceeded */
+  maxLength?: number;
+  /** The previous, too long translation */
+  previousTranslation?: string;
+}
+
+interface MemoryReference {
+  source: string;
+  target: string;
+  score: number;
 }
 
 interface TranslationResult {
@@ -19,6 +38,19 @@ interface BatchTranslationRequest {
   sourceLocale: string;
   targetLocales: string[];
   context?: string;
+  /** Prompt names per locale code, provided by the CLI's locale registry */
+  localeNames?: Record<string, string>;
+  /** Translation memory references per target locale and field */
+  memory?: Record<string, Record<string, MemoryReference[]>>;
+  /** Project glossary: protected terms, and fixed renderings per target locale */
+  glossary?: {
+    doNotTranslate: string[];
+    terms: Record<string, Record<string, string>>;
+  };
+  /** Style guide instructions per target locale */
+  style?: Record<string, string[]>;
+  /** Fields whose previous translation was over the limit, with that limit (single target locale) */
+  shorten?: Record<string, { maxLength: number; previous: string }>;
 }
 
 interface BatchTranslationResult {
@@ -52,28 +84,13 @@ class OpenAIService {
     }
   }
   
-  private getLocaleDisplayName(locale: string): string {
-    const localeMap: Record<string, string> = {
-      'en-US': 'English',
-      'zh-Hans': 'Simplified Chinese',
-      'zh-Hant': 'Traditional Chinese',
-      'fr-FR': 'French',
-      'de-DE': 'German',
-      'ja-JP': 'Japanese',
-      'ko-KR': 'Korean',
-      'es-ES': 'Spanish',
-      'pt-BR': 'Portuguese (Brazil)',
-      'it-IT': 'Italian',
-      'ru-RU': 'Russian',
-      'ar-SA': 'Arabic'
-    };
-    
-    return localeMap[locale] || locale;
+  private getLocaleDisplayName(locale: string, localeNames?: Record<string, string>): string {
+    return localeNames?.[locale] || locale;
   }
   
   private buildTranslationPrompt(request: TranslationRequest): string {
-    const sourceLanguage = this.getLocaleDisplayName(request.sourceLocale);
-    const targetLanguage = this.getLocaleDisplayName(request.targetLocale);
+    const sourceLanguage = this.getLocaleDisplayName(request.sourceLocale, request.localeNames);
+    const targetLanguage = this.getLocaleDisplayName(request.targetLocale, request.localeNames);
     
     let prompt = `You are a professional app store translator. Translate the following ${sourceLanguage} text to ${targetLanguage}.
 
@@ -103,6 +120,45 @@ Important guidelines:
       prompt += `Additional context: ${request.context}\n\n`;
     }
     
+    if (request.references?.length) {
+      prompt += `Previously approved translations of similar text (reuse their wording and terminology where it fits):\n`;
+      for (const reference of request.references) {
+        prompt += `- "${reference.source}" → "${reference.target}"\n`;
+      }
+      prompt += `\n`;
+    }
+    
+    if (request.style?.length) {
+      prompt += `Style guide for ${targetLanguage}:\n`;
+      for (const instruction of request.style) {
+        prompt += `- ${instruction}\n`;
+      }
+      prompt += `\n`;
+    }
+    
+    if (request.doNotTranslate?.length) {
+      prompt += `Never translate these terms; keep them exactly as written: ${request.doNotTranslate.map(term => `"${term}"`).join(', ')}\n\n`;
+    }
+    
+    const terms = Object.entries(request.terms ?? {});
+    if (terms.length) {
+      prompt += `Always use these fixed translations:\n`;
+      for (const [term, rendering] of terms) {
+        prompt += `- "${term}" → "${rendering}"\n`;
+      }
+      prompt += `\n`;
+    }
+    
+    if (request.maxLength && request.previousTranslation !== undefined) {
+      const previousLength = Array.from(request.previousTranslation).length;
+      prompt += `Your previous translation was too long: "${request.previousTranslation}" (${previousLength} characters).
+The translation must be at most ${request.maxLength} characters, counting every letter, space and punctuation mark. Rephrase or drop less important words instead of cutting the text off.\n`;
+      if (request.field === 'keywords') {
+        prompt += `Keep it a comma-separated list without spaces after commas, dropping the least important keywords first.\n`;
+      }
+      prompt += `\n`;
+    }
+    
     prompt += `Text to translate:\n"${request.text}"
 
 Respond with ONLY the translated text, no explanations or additional commentary.`;
@@ -179,7 +235,14 @@ Respond with ONLY the translated text, no explanations or additional commentary.
             sourceLocale: request.sourceLocale,
             targetLocale: targetLocale,
             context: request.context,
-            field: field
+            field: field,
+            localeNames: request.localeNames,
+            references: request.memory?.[targetLocale]?.[field],
+            doNotTranslate: request.glossary?.doNotTranslate,
+            terms: request.glossary?.terms[targetLocale],
+            style: request.style?.[targetLocale],
+            maxLength: request.shorten?.[field]?.maxLength,
+            previousTranslation: request.shorten?.[field]?.previous
           });
           
           translations[targetLocale][field] = translationResult.translatedText;